@@ -1,6 +1,8 @@
-use lifeready_audit::zero_hash;
+use chrono::{DateTime, Utc};
+pub use lifeready_audit::AuditKeyring;
+use lifeready_audit::{canonical_json, chain_hash, export_binding_sha256, zero_hash};
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::{BufRead, BufReader};
@@ -21,19 +23,54 @@ pub struct AuditEvent {
     pub created_at: String,
     pub prev_hash: String,
     pub event_hash: String,
+    /// Key epoch whose HMAC secret produced `event_hash`; absent for unkeyed legacy events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
     pub event: AuditAppend,
 }
 
+/// Newest `manifest.json` schema this verifier understands. Manifests written before the
+/// field existed carry no `schema_version` and are treated as version 1.
+pub const MANIFEST_SCHEMA_VERSION: u32 = 4;
+
+/// Fields serialize in declaration order and new fields are only ever appended, so a
+/// given schema version always produces byte-identical JSON for the same export.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ExportManifest {
+    #[serde(default = "legacy_manifest_schema_version")]
+    pub schema_version: u32,
     pub case_id: String,
     pub case_type: String,
     pub exported_at: String,
     pub audit_head_hash: String,
     pub audit_events_sha256: String,
     pub documents: Vec<ManifestDocument>,
+    /// What `audit.jsonl` covers; added in schema_version 3. `case` marks an extract of
+    /// the global chain scoped to one case, absent means the whole chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_scope: Option<String>,
+    /// Commits to `audit_head_hash`, `audit_events_sha256` and every document checksum
+    /// together; required from schema_version 4. See [`ExportManifest::binding_sha256`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_binding_sha256: Option<String>,
+}
+
+impl ExportManifest {
+    /// Recomputes `export_binding_sha256` from the manifest's own checksums.
+    pub fn binding_sha256(&self) -> String {
+        export_binding_sha256(
+            &self.audit_head_hash,
+            &self.audit_events_sha256,
+            self.documents
+                .iter()
+                .map(|doc| (doc.sha256.as_str(), doc.bundle_path.as_str())),
+        )
+    }
 }
 
+/// `audit_scope` value for bundles carrying only the events that reference one case.
+pub const AUDIT_SCOPE_CASE: &str = "case";
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ManifestDocument {
     pub slot_name: String,
@@ -42,15 +79,69 @@ pub struct ManifestDocument {
     pub title: String,
     pub sha256: String,
     pub bundle_path: String,
+    /// Vault version bundled; added in schema_version 2 and absent for generated files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
 }
 
+/// Detached Ed25519 signature over the exact `manifest.json` bytes, stored beside the
+/// manifest as `manifest.json.sig`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ManifestSignature {
+    pub algorithm: String,
+    pub key_fingerprint: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+pub const MANIFEST_SIGNATURE_FILE: &str = "manifest.json.sig";
+
+/// Verifies a chain of unkeyed events. Chains containing HMAC-keyed events need
+/// [`verify_audit_chain_with_keys`].
 pub fn verify_audit_chain(input: &Path, expected_head: Option<&str>) -> Result<String, String> {
+    verify_audit_chain_with_keys(input, expected_head, &AuditKeyring::default())
+}
+
+/// Verifies each event with its key epoch's secret from `keys`. Once a keyed event has
+/// been seen, later unkeyed events are rejected, so a rewritten tail cannot downgrade to
+/// plain SHA-256 hashes that need no secret to forge. When `keys` dates its epochs, any
+/// unkeyed event created at or after the first activation is rejected too, which also
+/// catches a chain re-hashed from the start without keys.
+pub fn verify_audit_chain_with_keys(
+    input: &Path,
+    expected_head: Option<&str>,
+    keys: &AuditKeyring,
+) -> Result<String, String> {
+    verify_events(input, expected_head, keys, true)
+}
+
+/// Verifies an extract of the chain, such as the events a case export bundles. Every
+/// event must still hash correctly over its own `prev_hash`, but consecutive events may
+/// skip over events left out of the extract, so the first event need not start at the
+/// zero hash.
+pub fn verify_audit_extract_with_keys(
+    input: &Path,
+    expected_head: Option<&str>,
+    keys: &AuditKeyring,
+) -> Result<String, String> {
+    verify_events(input, expected_head, keys, false)
+}
+
+fn verify_events(
+    input: &Path,
+    expected_head: Option<&str>,
+    keys: &AuditKeyring,
+    contiguous: bool,
+) -> Result<String, String> {
     let file = fs::File::open(input)
         .map_err(|error| format!("Failed to open {}: {error}", input.display()))?;
     let reader = BufReader::new(file);
 
     let mut prev_hash = zero_hash();
     let mut last_hash = prev_hash.clone();
+    let mut last_line = 0;
+    let mut seen_keyed = false;
+    let keyed_since = keys.keyed_since();
 
     for (idx, line) in reader.lines().enumerate() {
         let line = line.map_err(|error| format!("Failed to read line {}: {error}", idx + 1))?;
@@ -60,38 +151,91 @@ pub fn verify_audit_chain(input: &Path, expected_head: Option<&str>) -> Result<S
         let event: AuditEvent =
             serde_json::from_str(&line).map_err(|_| format!("Invalid JSON at line {}", idx + 1))?;
 
-        if event.prev_hash != prev_hash {
+        if contiguous && event.prev_hash != prev_hash {
             return Err(format!(
                 "Chain break at line {}: prev_hash mismatch",
                 idx + 1
             ));
         }
 
-        let computed = compute_event_hash(&prev_hash, &event);
+        let key =
+            match &event.key_id {
+                Some(key_id) => {
+                    seen_keyed = true;
+                    Some(keys.get(key_id).ok_or_else(|| {
+                        format!("Unknown audit key '{key_id}' at line {}", idx + 1)
+                    })?)
+                }
+                None if seen_keyed || created_in_epoch(&event, keyed_since) => {
+                    return Err(format!(
+                        "Unkeyed event after a key epoch at line {}",
+                        idx + 1
+                    ));
+                }
+                None => None,
+            };
+
+        let computed = compute_event_hash(&event.prev_hash, &event, key);
         if computed != event.event_hash {
             return Err(format!("Hash mismatch at line {}", idx + 1));
         }
 
         prev_hash = event.event_hash.clone();
         last_hash = prev_hash.clone();
+        last_line = idx + 1;
     }
 
     if let Some(expected) = expected_head
         && expected != last_hash
     {
         return Err(format!(
-            "Head hash mismatch: expected {expected}, got {last_hash}"
+            "Head hash mismatch at line {last_line}: expected {expected}, got {last_hash}"
         ));
     }
 
     Ok(last_hash)
 }
 
-pub fn verify_manifest(manifest_path: &Path, bundle_dir: Option<&Path>) -> Result<(), String> {
+/// Whether `event` was created at or after `keyed_since`. An unparsable `created_at`
+/// counts as inside the epoch, so it cannot dodge the check.
+fn created_in_epoch(event: &AuditEvent, keyed_since: Option<DateTime<Utc>>) -> bool {
+    let Some(keyed_since) = keyed_since else {
+        return false;
+    };
+    DateTime::parse_from_rfc3339(&event.created_at)
+        .map(|created_at| created_at >= keyed_since)
+        .unwrap_or(true)
+}
+
+fn legacy_manifest_schema_version() -> u32 {
+    1
+}
+
+/// Reads `manifest.json`, refusing versions newer than [`MANIFEST_SCHEMA_VERSION`] before
+/// attempting to parse a shape this verifier may not know.
+fn read_manifest(manifest_path: &Path) -> Result<ExportManifest, String> {
     let manifest_bytes =
         fs::read(manifest_path).map_err(|error| format!("Failed to read manifest: {error}"))?;
-    let manifest: ExportManifest = serde_json::from_slice(&manifest_bytes)
+    let value: Value = serde_json::from_slice(&manifest_bytes)
         .map_err(|error| format!("Invalid manifest JSON: {error}"))?;
+    let schema_version = match value.get("schema_version") {
+        None => legacy_manifest_schema_version(),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .ok_or("Invalid schema_version in manifest")?,
+    };
+    if schema_version > MANIFEST_SCHEMA_VERSION {
+        return Err(format!(
+            "Unsupported manifest schema_version {schema_version}; this verifier supports up to {MANIFEST_SCHEMA_VERSION}"
+        ));
+    }
+    serde_json::from_value(value).map_err(|error| format!("Invalid manifest JSON: {error}"))
+}
+
+pub fn verify_manifest(manifest_path: &Path, bundle_dir: Option<&Path>) -> Result<(), String> {
+    let manifest = read_manifest(manifest_path)?;
 
     if !manifest.audit_head_hash.is_empty() && manifest.audit_head_hash.len() != 64 {
         return Err("Invalid audit_head_hash in manifest".into());
@@ -108,7 +252,12 @@ pub fn verify_manifest(manifest_path: &Path, bundle_dir: Option<&Path>) -> Resul
             .to_path_buf()
     });
 
+    // From schema_version 4 the manifest binds the audit chain to the documents, so a
+    // bundle without `audit.jsonl` is incomplete rather than merely unaudited.
     let audit_path = base_dir.join("audit.jsonl");
+    if manifest.schema_version >= 4 && !audit_path.exists() {
+        return Err("audit.jsonl missing from bundle".into());
+    }
     if audit_path.exists() {
         let audit_sha = sha256_file(&audit_path)?;
         if audit_sha != manifest.audit_events_sha256 {
@@ -127,29 +276,187 @@ pub fn verify_manifest(manifest_path: &Path, bundle_dir: Option<&Path>) -> Resul
     Ok(())
 }
 
+/// Result of re-fetching one manifest document from the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VaultCheckStatus {
+    Match,
+    Mismatch {
+        actual_sha256: String,
+    },
+    /// The vault could not produce the version (missing, forbidden, unreachable).
+    Unavailable {
+        error: String,
+    },
+    /// Generated files carry no `version_id` and were never stored in the vault.
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct VaultCheck {
+    pub document_id: String,
+    pub version_id: Option<String>,
+    pub bundle_path: String,
+    pub status: VaultCheckStatus,
+}
+
+impl VaultCheck {
+    pub fn passed(&self) -> bool {
+        matches!(
+            self.status,
+            VaultCheckStatus::Match | VaultCheckStatus::Skipped
+        )
+    }
+}
+
+/// Re-checks every vault document in the manifest against the vault's current copy.
+/// `fetch_sha256` is given a `document_id` and `version_id` and returns the hex SHA-256 of
+/// the bytes the vault serves for them.
+pub fn verify_against_vault(
+    manifest_path: &Path,
+    mut fetch_sha256: impl FnMut(&str, &str) -> Result<String, String>,
+) -> Result<Vec<VaultCheck>, String> {
+    let manifest = read_manifest(manifest_path)?;
+    Ok(manifest
+        .documents
+        .into_iter()
+        .map(|doc| {
+            let status = match &doc.version_id {
+                None => VaultCheckStatus::Skipped,
+                Some(version_id) => match fetch_sha256(&doc.document_id, version_id) {
+                    Ok(actual) if actual == doc.sha256 => VaultCheckStatus::Match,
+                    Ok(actual_sha256) => VaultCheckStatus::Mismatch { actual_sha256 },
+                    Err(error) => VaultCheckStatus::Unavailable { error },
+                },
+            };
+            VaultCheck {
+                document_id: doc.document_id,
+                version_id: doc.version_id,
+                bundle_path: doc.bundle_path,
+                status,
+            }
+        })
+        .collect())
+}
+
+/// Checks a bundle's `audit.jsonl` on its own: the file matches `audit_events_sha256`,
+/// every event re-hashes over its `prev_hash` and links to the one before it, and the
+/// last event is `audit_head_hash`. Returns the head hash; errors name the first line
+/// that breaks the chain.
+pub fn verify_chain(bundle_dir: &Path, keys: &AuditKeyring) -> Result<String, String> {
+    let manifest = read_manifest(&bundle_dir.join("manifest.json"))?;
+    let audit_path = bundle_dir.join("audit.jsonl");
+    if !audit_path.exists() {
+        return Err("audit.jsonl missing from bundle".into());
+    }
+    if sha256_file(&audit_path)? != manifest.audit_events_sha256 {
+        return Err("audit.jsonl checksum mismatch".into());
+    }
+    verify_bundled_chain(&audit_path, &manifest, keys)
+}
+
+/// Walks a bundled chain the way its `audit_scope` requires.
+fn verify_bundled_chain(
+    audit_path: &Path,
+    manifest: &ExportManifest,
+    keys: &AuditKeyring,
+) -> Result<String, String> {
+    match manifest.audit_scope.as_deref() {
+        None => verify_audit_chain_with_keys(audit_path, Some(&manifest.audit_head_hash), keys),
+        Some(AUDIT_SCOPE_CASE) => {
+            verify_audit_extract_with_keys(audit_path, Some(&manifest.audit_head_hash), keys)
+        }
+        Some(scope) => Err(format!("Unsupported audit_scope '{scope}' in manifest")),
+    }
+}
+
 pub fn verify_bundle(bundle_dir: &Path) -> Result<(), String> {
+    verify_bundle_with_keys(bundle_dir, &AuditKeyring::default())
+}
+
+/// [`verify_bundle`] for bundles whose audit chain includes HMAC-keyed events.
+pub fn verify_bundle_with_keys(bundle_dir: &Path, keys: &AuditKeyring) -> Result<(), String> {
     let manifest_path = bundle_dir.join("manifest.json");
-    let manifest_bytes =
-        fs::read(&manifest_path).map_err(|error| format!("Failed to read manifest: {error}"))?;
-    let manifest: ExportManifest = serde_json::from_slice(&manifest_bytes)
-        .map_err(|error| format!("Invalid manifest JSON: {error}"))?;
+    let manifest = read_manifest(&manifest_path)?;
 
     verify_manifest(&manifest_path, Some(bundle_dir))?;
 
     let audit_path = bundle_dir.join("audit.jsonl");
     if audit_path.exists() {
-        verify_audit_chain(&audit_path, Some(&manifest.audit_head_hash))?;
+        verify_bundled_chain(&audit_path, &manifest, keys)?;
     }
 
+    verify_export_binding(&manifest)?;
+    verify_manifest_signature(bundle_dir)?;
+
     Ok(())
 }
 
-fn compute_event_hash(prev_hash: &str, event: &AuditEvent) -> String {
-    let canonical = canonical_event_json(event);
-    let mut hasher = Sha256::new();
-    hasher.update(prev_hash.as_bytes());
-    hasher.update(canonical.as_bytes());
-    hex::encode(hasher.finalize())
+/// Once the files match the manifest's checksums and the chain ends at its head hash,
+/// checks that those three values are the ones bound together at export time
+/// (schema_version 4+). Older manifests carry no binding and pass unchanged.
+fn verify_export_binding(manifest: &ExportManifest) -> Result<(), String> {
+    if manifest.schema_version < 4 {
+        return Ok(());
+    }
+    let binding = manifest
+        .export_binding_sha256
+        .as_deref()
+        .ok_or("Missing export_binding_sha256 in manifest")?;
+    if binding != manifest.binding_sha256() {
+        return Err(
+            "export_binding_sha256 does not match the manifest's audit and document checksums"
+                .into(),
+        );
+    }
+    Ok(())
+}
+
+/// Checks `manifest.json.sig` against `manifest.json` when the bundle is signed. Returns
+/// the signer's key fingerprint, or `None` for unsigned bundles. The signature only
+/// proves who produced the bundle if the caller also pins the expected fingerprint.
+pub fn verify_manifest_signature(bundle_dir: &Path) -> Result<Option<String>, String> {
+    use ed25519_dalek::{Signature, VerifyingKey};
+
+    let signature_path = bundle_dir.join(MANIFEST_SIGNATURE_FILE);
+    if !signature_path.exists() {
+        return Ok(None);
+    }
+    let signature_bytes = fs::read(&signature_path)
+        .map_err(|error| format!("Failed to read {MANIFEST_SIGNATURE_FILE}: {error}"))?;
+    let signature: ManifestSignature = serde_json::from_slice(&signature_bytes)
+        .map_err(|error| format!("Invalid {MANIFEST_SIGNATURE_FILE}: {error}"))?;
+    if signature.algorithm != "ed25519" {
+        return Err(format!(
+            "Unsupported signature algorithm {}",
+            signature.algorithm
+        ));
+    }
+
+    let public_key: [u8; 32] = hex::decode(&signature.public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Invalid public_key in manifest signature")?;
+    if hex::encode(Sha256::digest(public_key)) != signature.key_fingerprint {
+        return Err("Signature key_fingerprint does not match public_key".into());
+    }
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|_| "Invalid public_key in manifest signature")?;
+    let signature_raw: [u8; 64] = hex::decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Invalid signature in manifest signature")?;
+
+    let manifest_bytes = fs::read(bundle_dir.join("manifest.json"))
+        .map_err(|error| format!("Failed to read manifest: {error}"))?;
+    verifying_key
+        .verify_strict(&manifest_bytes, &Signature::from_bytes(&signature_raw))
+        .map_err(|_| "Manifest signature verification failed")?;
+
+    Ok(Some(signature.key_fingerprint))
+}
+
+fn compute_event_hash(prev_hash: &str, event: &AuditEvent, key: Option<&[u8]>) -> String {
+    chain_hash(prev_hash, &canonical_event_json(event), key)
 }
 
 fn canonical_event_json(event: &AuditEvent) -> String {
@@ -162,26 +469,7 @@ fn canonical_event_json(event: &AuditEvent) -> String {
         "case_id": event.event.case_id,
         "payload": event.event.payload,
     });
-    let canonical_value = canonicalize_value(&value);
-    serde_json::to_string(&canonical_value).unwrap_or_default()
-}
-
-fn canonicalize_value(value: &Value) -> Value {
-    match value {
-        Value::Object(map) => {
-            let mut keys: Vec<String> = map.keys().cloned().collect();
-            keys.sort();
-            let mut ordered = Map::new();
-            for key in keys {
-                if let Some(inner) = map.get(&key) {
-                    ordered.insert(key, canonicalize_value(inner));
-                }
-            }
-            Value::Object(ordered)
-        }
-        Value::Array(items) => Value::Array(items.iter().map(canonicalize_value).collect()),
-        _ => value.clone(),
-    }
+    canonical_json(&value)
 }
 
 fn resolve_bundle_path(base_dir: &Path, bundle_path: &str) -> Result<PathBuf, String> {
@@ -217,6 +505,13 @@ mod tests {
         std::env::temp_dir().join(format!("{name}-{}-{}", std::process::id(), nanos))
     }
 
+    impl ExportManifest {
+        fn bound(mut self) -> Self {
+            self.export_binding_sha256 = Some(self.binding_sha256());
+            self
+        }
+    }
+
     fn write_audit_line(path: &Path, event: &AuditEvent) {
         let line = serde_json::to_string(event).unwrap();
         fs::write(path, format!("{line}\n")).unwrap();
@@ -233,6 +528,7 @@ mod tests {
             created_at: "2025-01-01T00:00:00Z".into(),
             prev_hash: zero_hash(),
             event_hash: "".into(),
+            key_id: None,
             event: AuditAppend {
                 actor_principal_id: "actor".into(),
                 action: "case.export".into(),
@@ -241,7 +537,7 @@ mod tests {
                 payload: serde_json::json!({"ok": true}),
             },
         };
-        event.event_hash = compute_event_hash(&event.prev_hash, &event);
+        event.event_hash = compute_event_hash(&event.prev_hash, &event, None);
         write_audit_line(&path, &event);
 
         let head = verify_audit_chain(&path, None).expect("head");
@@ -259,6 +555,7 @@ mod tests {
             created_at: "2025-01-01T00:00:00Z".into(),
             prev_hash: zero_hash(),
             event_hash: "".into(),
+            key_id: None,
             event: AuditAppend {
                 actor_principal_id: "actor".into(),
                 action: "case.export".into(),
@@ -267,7 +564,7 @@ mod tests {
                 payload: serde_json::json!({"ok": true}),
             },
         };
-        event.event_hash = compute_event_hash(&event.prev_hash, &event);
+        event.event_hash = compute_event_hash(&event.prev_hash, &event, None);
         write_audit_line(&path, &event);
 
         let err = verify_audit_chain(&path, Some("bad")).expect_err("should fail");
@@ -291,6 +588,7 @@ mod tests {
             created_at: "2025-01-01T00:00:00Z".into(),
             prev_hash: zero_hash(),
             event_hash: "".into(),
+            key_id: None,
             event: AuditAppend {
                 actor_principal_id: "actor".into(),
                 action: "case.export".into(),
@@ -299,11 +597,12 @@ mod tests {
                 payload: serde_json::json!({"ok": true}),
             },
         };
-        event.event_hash = compute_event_hash(&event.prev_hash, &event);
+        event.event_hash = compute_event_hash(&event.prev_hash, &event, None);
         write_audit_line(&audit_path, &event);
         let audit_sha = sha256_file(&audit_path).unwrap();
 
         let manifest = ExportManifest {
+            schema_version: MANIFEST_SCHEMA_VERSION,
             case_id: "case-1".into(),
             case_type: "mhca39".into(),
             exported_at: "2025-01-01T00:00:00Z".into(),
@@ -316,8 +615,12 @@ mod tests {
                 title: "Doc".into(),
                 sha256: doc_sha,
                 bundle_path: "documents/doc-1".into(),
+                version_id: Some("version-1".into()),
             }],
-        };
+            audit_scope: None,
+            export_binding_sha256: None,
+        }
+        .bound();
         let manifest_path = dir.join("manifest.json");
         fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
 
@@ -334,6 +637,7 @@ mod tests {
                 created_at: format!("2025-01-01T00:00:{:02}Z", i),
                 prev_hash: prev.clone(),
                 event_hash: "".into(),
+                key_id: None,
                 event: AuditAppend {
                     actor_principal_id: "actor".into(),
                     action: format!("action.{i}"),
@@ -342,7 +646,7 @@ mod tests {
                     payload: serde_json::json!({"step": i}),
                 },
             };
-            event.event_hash = compute_event_hash(&event.prev_hash, &event);
+            event.event_hash = compute_event_hash(&event.prev_hash, &event, None);
             prev = event.event_hash.clone();
             events.push(event);
         }
@@ -370,6 +674,7 @@ mod tests {
         let head_hash = events.last().unwrap().event_hash.clone();
 
         let manifest = ExportManifest {
+            schema_version: MANIFEST_SCHEMA_VERSION,
             case_id: "case-1".into(),
             case_type: "mhca39".into(),
             exported_at: "2025-01-01T00:00:00Z".into(),
@@ -382,8 +687,12 @@ mod tests {
                 title: "Subject ID".into(),
                 sha256: doc_sha,
                 bundle_path: "documents/doc-1".into(),
+                version_id: Some("version-1".into()),
             }],
-        };
+            audit_scope: None,
+            export_binding_sha256: None,
+        }
+        .bound();
 
         let manifest_path = dir.join("manifest.json");
         fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
@@ -528,6 +837,7 @@ mod tests {
         let audit_sha = sha256_file(&audit_path).unwrap();
 
         let manifest = ExportManifest {
+            schema_version: MANIFEST_SCHEMA_VERSION,
             case_id: "case-2".into(),
             case_type: "mhca39".into(),
             exported_at: "2025-01-01T00:00:00Z".into(),
@@ -541,6 +851,7 @@ mod tests {
                     title: "Doc 1".into(),
                     sha256: doc1_sha,
                     bundle_path: "documents/doc-1".into(),
+                    version_id: Some("version-1".into()),
                 },
                 ManifestDocument {
                     slot_name: "id_applicant".into(),
@@ -549,13 +860,332 @@ mod tests {
                     title: "Doc 2".into(),
                     sha256: doc2_sha,
                     bundle_path: "documents/doc-2".into(),
+                    version_id: Some("version-2".into()),
                 },
             ],
-        };
+            audit_scope: None,
+            export_binding_sha256: None,
+        }
+        .bound();
 
         let manifest_path = dir.join("manifest.json");
         fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
 
         verify_bundle(&dir).expect("valid bundle");
     }
+
+    #[test]
+    fn manifest_without_schema_version_is_treated_as_v1() {
+        let dir = unique_dir("legacy-manifest");
+        build_bundle(&dir);
+        let manifest_path = dir.join("manifest.json");
+        let mut manifest: serde_json::Value =
+            serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+        manifest.as_object_mut().unwrap().remove("schema_version");
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        verify_bundle(&dir).expect("legacy manifest verifies");
+    }
+
+    #[test]
+    fn manifest_with_future_schema_version_is_rejected() {
+        let dir = unique_dir("future-manifest");
+        build_bundle(&dir);
+        let manifest_path = dir.join("manifest.json");
+        let mut manifest: serde_json::Value =
+            serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+        manifest["schema_version"] = serde_json::json!(MANIFEST_SCHEMA_VERSION + 1);
+        manifest["documents"] = serde_json::json!("reshaped in a later version");
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        let err = verify_bundle(&dir).unwrap_err();
+        assert!(err.contains("Unsupported manifest schema_version"), "{err}");
+    }
+
+    fn sign_bundle(dir: &Path, key: &ed25519_dalek::SigningKey) -> String {
+        use ed25519_dalek::Signer;
+
+        let manifest_bytes = fs::read(dir.join("manifest.json")).unwrap();
+        let public_key = key.verifying_key().to_bytes();
+        let fingerprint = hex::encode(Sha256::digest(public_key));
+        let signature = ManifestSignature {
+            algorithm: "ed25519".into(),
+            key_fingerprint: fingerprint.clone(),
+            public_key: hex::encode(public_key),
+            signature: hex::encode(key.sign(&manifest_bytes).to_bytes()),
+        };
+        fs::write(
+            dir.join(MANIFEST_SIGNATURE_FILE),
+            serde_json::to_vec(&signature).unwrap(),
+        )
+        .unwrap();
+        fingerprint
+    }
+
+    #[test]
+    fn signed_bundle_verifies_and_reports_signer() {
+        let dir = unique_dir("signed-bundle");
+        build_bundle(&dir);
+        assert_eq!(verify_manifest_signature(&dir).unwrap(), None);
+
+        let fingerprint = sign_bundle(&dir, &ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+
+        verify_bundle(&dir).expect("signed bundle");
+        assert_eq!(verify_manifest_signature(&dir).unwrap(), Some(fingerprint));
+    }
+
+    #[test]
+    fn tampered_signed_manifest_detected() {
+        let dir = unique_dir("signed-tamper");
+        let (mut manifest, _) = build_bundle(&dir);
+        sign_bundle(&dir, &ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+
+        manifest.exported_at = "2026-01-01T00:00:00Z".into();
+        fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let err = verify_bundle(&dir).expect_err("should fail");
+        assert_eq!(err, "Manifest signature verification failed");
+    }
+
+    #[test]
+    fn signature_with_swapped_public_key_detected() {
+        let dir = unique_dir("signed-swap");
+        build_bundle(&dir);
+        sign_bundle(&dir, &ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+
+        let path = dir.join(MANIFEST_SIGNATURE_FILE);
+        let mut signature: ManifestSignature =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let other = ed25519_dalek::SigningKey::from_bytes(&[9; 32]).verifying_key();
+        signature.public_key = hex::encode(other.to_bytes());
+        fs::write(&path, serde_json::to_vec(&signature).unwrap()).unwrap();
+
+        let err = verify_bundle(&dir).expect_err("should fail");
+        assert!(err.contains("key_fingerprint does not match"));
+    }
+
+    fn keyed_chain(epochs: &[Option<&str>], keys: &AuditKeyring) -> Vec<AuditEvent> {
+        let mut events = build_chain(epochs.len());
+        let mut prev = zero_hash();
+        for (event, key_id) in events.iter_mut().zip(epochs) {
+            event.prev_hash = prev.clone();
+            event.key_id = key_id.map(str::to_string);
+            let key = key_id.map(|key_id| keys.get(key_id).unwrap());
+            event.event_hash = compute_event_hash(&event.prev_hash, event, key);
+            prev = event.event_hash.clone();
+        }
+        events
+    }
+
+    #[test]
+    fn verify_audit_chain_with_keys_checks_each_epoch() {
+        let dir = unique_dir("audit-keyed");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let keys =
+            AuditKeyring::parse(&format!("k1:{},k2:{}", "11".repeat(32), "22".repeat(32))).unwrap();
+
+        let events = keyed_chain(&[None, Some("k1"), Some("k2")], &keys);
+        write_chain(&path, &events);
+        let head = verify_audit_chain_with_keys(&path, None, &keys).expect("keyed chain");
+        assert_eq!(head, events[2].event_hash);
+
+        let err = verify_audit_chain(&path, None).expect_err("keys required");
+        assert!(err.contains("Unknown audit key 'k1' at line 2"));
+
+        let wrong =
+            AuditKeyring::parse(&format!("k1:{},k2:{}", "11".repeat(32), "33".repeat(32))).unwrap();
+        let err = verify_audit_chain_with_keys(&path, None, &wrong).expect_err("wrong key");
+        assert!(err.contains("Hash mismatch at line 3"));
+    }
+
+    #[test]
+    fn verify_audit_chain_with_keys_rejects_downgrade_after_epoch() {
+        let dir = unique_dir("audit-downgrade");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let keys = AuditKeyring::parse(&format!("k1:{}", "11".repeat(32))).unwrap();
+
+        let events = keyed_chain(&[Some("k1"), None], &keys);
+        write_chain(&path, &events);
+        let err = verify_audit_chain_with_keys(&path, None, &keys).expect_err("downgrade");
+        assert!(err.contains("Unkeyed event after a key epoch at line 2"));
+    }
+
+    #[test]
+    fn verify_audit_chain_with_keys_rejects_chain_rehashed_without_keys() {
+        let dir = unique_dir("audit-rehashed");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let keys =
+            AuditKeyring::parse(&format!("k1:{}@2025-01-01T00:00:01Z", "11".repeat(32))).unwrap();
+
+        let events = keyed_chain(&[None, Some("k1"), Some("k1")], &keys);
+        write_chain(&path, &events);
+        verify_audit_chain_with_keys(&path, None, &keys).expect("genuine chain");
+
+        // Rewriting every event with plain SHA-256 needs no secret and links cleanly.
+        let mut forged = build_chain(3);
+        forged[2].event.payload = serde_json::json!({"step": "rewritten"});
+        let mut prev = zero_hash();
+        for event in forged.iter_mut() {
+            event.prev_hash = prev.clone();
+            event.event_hash = compute_event_hash(&event.prev_hash, event, None);
+            prev = event.event_hash.clone();
+        }
+        write_chain(&path, &forged);
+        verify_audit_chain(&path, None).expect("links without an epoch date");
+        let err = verify_audit_chain_with_keys(&path, None, &keys).expect_err("rehashed");
+        assert!(err.contains("Unkeyed event after a key epoch at line 2"));
+    }
+
+    #[test]
+    fn extract_verifies_across_gaps_but_not_tampering() {
+        let dir = unique_dir("extract-chain");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let events = build_chain(4);
+        let extract = [&events[1], &events[3]];
+        let lines: Vec<String> = extract
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap())
+            .collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let keys = AuditKeyring::default();
+        let head = verify_audit_extract_with_keys(&path, None, &keys).unwrap();
+        assert_eq!(head, events[3].event_hash);
+        assert!(verify_audit_chain(&path, None).is_err());
+
+        let mut tampered = serde_json::to_value(&events[3]).unwrap();
+        tampered["event"]["payload"] = serde_json::json!({"step": 99});
+        fs::write(&path, format!("{}\n{tampered}\n", lines[0])).unwrap();
+        let err = verify_audit_extract_with_keys(&path, None, &keys).unwrap_err();
+        assert!(err.contains("Hash mismatch at line 2"));
+    }
+
+    #[test]
+    fn case_scoped_bundle_verifies_as_extract() {
+        let dir = unique_dir("case-scoped-bundle");
+        let (mut manifest, _) = build_bundle(&dir);
+        let events = build_chain(3);
+        write_chain(&dir.join("audit.jsonl"), &events[1..]);
+        manifest.audit_events_sha256 = sha256_file(&dir.join("audit.jsonl")).unwrap();
+        manifest.audit_head_hash = events[2].event_hash.clone();
+        manifest.export_binding_sha256 = Some(manifest.binding_sha256());
+        let manifest_path = dir.join("manifest.json");
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+        assert!(verify_bundle(&dir).is_err());
+
+        manifest.audit_scope = Some(AUDIT_SCOPE_CASE.into());
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+        verify_bundle(&dir).expect("case-scoped bundle");
+
+        manifest.audit_scope = Some("tenant".into());
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+        let err = verify_bundle(&dir).unwrap_err();
+        assert!(err.contains("Unsupported audit_scope"));
+    }
+
+    #[test]
+    fn swapped_audit_chain_fails_binding_check() {
+        let dir = unique_dir("swapped-audit");
+        let (mut manifest, _) = build_bundle(&dir);
+        verify_bundle(&dir).expect("original bundle");
+
+        // A different, internally valid chain with the manifest's audit fields updated
+        // to match still contradicts the binding recorded at export time.
+        let events = build_chain(2);
+        write_chain(&dir.join("audit.jsonl"), &events);
+        manifest.audit_events_sha256 = sha256_file(&dir.join("audit.jsonl")).unwrap();
+        manifest.audit_head_hash = events[1].event_hash.clone();
+        let manifest_path = dir.join("manifest.json");
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        let err = verify_bundle(&dir).unwrap_err();
+        assert!(err.contains("export_binding_sha256"), "{err}");
+    }
+
+    #[test]
+    fn bound_manifest_requires_audit_jsonl() {
+        let dir = unique_dir("missing-audit");
+        build_bundle(&dir);
+        fs::remove_file(dir.join("audit.jsonl")).unwrap();
+
+        let err = verify_bundle(&dir).unwrap_err();
+        assert!(err.contains("audit.jsonl missing"), "{err}");
+    }
+
+    #[test]
+    fn bound_manifest_requires_binding_field() {
+        let dir = unique_dir("unbound-manifest");
+        let (mut manifest, _) = build_bundle(&dir);
+        manifest.export_binding_sha256 = None;
+        fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let err = verify_bundle(&dir).unwrap_err();
+        assert!(err.contains("Missing export_binding_sha256"), "{err}");
+    }
+
+    #[test]
+    fn verify_against_vault_reports_each_document() {
+        let dir = unique_dir("vault-recheck");
+        fs::create_dir_all(&dir).unwrap();
+        let manifest = serde_json::json!({
+            "case_id": "case-1",
+            "case_type": "mhca39",
+            "exported_at": "2025-01-01T00:00:00Z",
+            "audit_head_hash": "",
+            "audit_events_sha256": "a".repeat(64),
+            "documents": [
+                {"slot_name": "a", "document_id": "doc-1", "document_type": "id", "title": "A",
+                 "sha256": "1".repeat(64), "bundle_path": "documents/a", "version_id": "v-1"},
+                {"slot_name": "b", "document_id": "doc-2", "document_type": "id", "title": "B",
+                 "sha256": "2".repeat(64), "bundle_path": "documents/b", "version_id": "v-2"},
+                {"slot_name": "c", "document_id": "doc-3", "document_type": "id", "title": "C",
+                 "sha256": "3".repeat(64), "bundle_path": "documents/c", "version_id": "v-3"},
+                {"slot_name": "pdf", "document_id": "case-1", "document_type": "instructions_pdf",
+                 "title": "pack.pdf", "sha256": "4".repeat(64), "bundle_path": "pack.pdf"}
+            ]
+        });
+        let manifest_path = dir.join("manifest.json");
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        let mut fetched = Vec::new();
+        let checks = verify_against_vault(&manifest_path, |document_id, version_id| {
+            fetched.push(format!("{document_id}/{version_id}"));
+            match document_id {
+                "doc-1" => Ok("1".repeat(64)),
+                "doc-2" => Ok("f".repeat(64)),
+                _ => Err("vault returned 404".into()),
+            }
+        })
+        .expect("checks");
+
+        assert_eq!(fetched, vec!["doc-1/v-1", "doc-2/v-2", "doc-3/v-3"]);
+        let statuses: Vec<_> = checks.iter().map(|check| check.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                VaultCheckStatus::Match,
+                VaultCheckStatus::Mismatch {
+                    actual_sha256: "f".repeat(64)
+                },
+                VaultCheckStatus::Unavailable {
+                    error: "vault returned 404".into()
+                },
+                VaultCheckStatus::Skipped,
+            ]
+        );
+        let passed: Vec<_> = checks.iter().map(VaultCheck::passed).collect();
+        assert_eq!(passed, vec![true, false, false, true]);
+    }
 }
@@ -1,7 +1,11 @@
 use clap::{Parser, Subcommand};
+use sha2::{Digest, Sha256};
 use std::path::PathBuf;
 
-use audit_verifier::{verify_audit_chain, verify_bundle, verify_manifest};
+use audit_verifier::{
+    AuditKeyring, VaultCheck, VaultCheckStatus, verify_against_vault, verify_audit_chain_with_keys,
+    verify_bundle_with_keys, verify_chain, verify_manifest, verify_manifest_signature,
+};
 
 #[derive(Debug, Parser)]
 #[command(name = "audit-verifier")]
@@ -18,6 +22,10 @@ enum Command {
         input: PathBuf,
         #[arg(long)]
         head_hash: Option<String>,
+        /// File of `key_id:hex_secret[@activated_at]` entries for HMAC-keyed events;
+        /// defaults to `AUDIT_HMAC_KEYS`.
+        #[arg(long)]
+        audit_keys: Option<PathBuf>,
     },
     VerifyManifest {
         #[arg(long)]
@@ -28,13 +36,118 @@ enum Command {
     VerifyBundle {
         #[arg(long)]
         bundle: PathBuf,
+        /// Require the manifest to be signed by the key with this SHA-256 fingerprint.
+        #[arg(long)]
+        signer: Option<String>,
+        /// File of `key_id:hex_secret[@activated_at]` entries for HMAC-keyed events;
+        /// defaults to `AUDIT_HMAC_KEYS`.
+        #[arg(long)]
+        audit_keys: Option<PathBuf>,
     },
+    /// Check only a bundle's audit chain: checksum, event linkage and head hash.
+    VerifyChain {
+        #[arg(long)]
+        bundle: PathBuf,
+        /// File of `key_id:hex_secret[@activated_at]` entries for HMAC-keyed events;
+        /// defaults to `AUDIT_HMAC_KEYS`.
+        #[arg(long)]
+        audit_keys: Option<PathBuf>,
+    },
+    /// Re-download every vault document in a manifest and compare it with the exported
+    /// checksum, confirming an archived pack still matches the vault.
+    VerifyAgainstVault {
+        #[arg(long)]
+        manifest: PathBuf,
+        /// Vault service base URL, e.g. `https://api.lifeready.local/vault`.
+        #[arg(long)]
+        vault_url: String,
+        /// Bearer token with read access to the documents; defaults to `VAULT_TOKEN`.
+        #[arg(long)]
+        token: Option<String>,
+    },
+}
+
+/// Entries in the keys file may be separated by commas or newlines.
+fn load_audit_keys(path: Option<&PathBuf>) -> Result<AuditKeyring, String> {
+    match path {
+        Some(path) => {
+            let spec = std::fs::read_to_string(path)
+                .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+            AuditKeyring::parse(&spec.replace('\n', ","))
+        }
+        None => AuditKeyring::from_env(),
+    }
+}
+
+/// Streams one version from the vault's download endpoint through SHA-256.
+fn fetch_vault_sha256(
+    client: &reqwest::blocking::Client,
+    vault_url: &str,
+    token: &str,
+    document_id: &str,
+    version_id: &str,
+) -> Result<String, String> {
+    let mut response = client
+        .get(format!(
+            "{}/v1/documents/{document_id}/download",
+            vault_url.trim_end_matches('/')
+        ))
+        .query(&[("version_id", version_id)])
+        .bearer_auth(token)
+        .send()
+        .map_err(|error| format!("request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("vault returned {}", response.status()));
+    }
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut response, &mut hasher)
+        .map_err(|error| format!("download failed: {error}"))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// One row per document; fails if any document mismatched or could not be fetched.
+fn report_vault_checks(checks: &[VaultCheck]) -> Result<(), String> {
+    println!(
+        "{:<36}  {:<36}  {:<8}  PATH",
+        "DOCUMENT", "VERSION", "RESULT"
+    );
+    for check in checks {
+        let result = match &check.status {
+            VaultCheckStatus::Match => "pass".to_string(),
+            VaultCheckStatus::Mismatch { actual_sha256 } => {
+                format!("FAIL (vault sha256 {actual_sha256})")
+            }
+            VaultCheckStatus::Unavailable { error } => format!("FAIL ({error})"),
+            VaultCheckStatus::Skipped => "skip".to_string(),
+        };
+        println!(
+            "{:<36}  {:<36}  {:<8}  {}",
+            check.document_id,
+            check.version_id.as_deref().unwrap_or("-"),
+            result,
+            check.bundle_path
+        );
+    }
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    if failed > 0 {
+        return Err(format!(
+            "{failed} of {} documents do not match the vault",
+            checks.len()
+        ));
+    }
+    println!("All vault documents match the manifest.");
+    Ok(())
 }
 
 fn run(args: Args) -> Result<(), String> {
     match args.command {
-        Command::VerifyAudit { input, head_hash } => {
-            verify_audit_chain(&input, head_hash.as_deref()).map(|head| {
+        Command::VerifyAudit {
+            input,
+            head_hash,
+            audit_keys,
+        } => {
+            let keys = load_audit_keys(audit_keys.as_ref())?;
+            verify_audit_chain_with_keys(&input, head_hash.as_deref(), &keys).map(|head| {
                 println!("Audit chain OK. Head hash: {}", head);
             })
         }
@@ -44,9 +157,49 @@ fn run(args: Args) -> Result<(), String> {
         } => verify_manifest(&manifest, bundle_dir.as_deref()).map(|_| {
             println!("Manifest OK.");
         }),
-        Command::VerifyBundle { bundle } => verify_bundle(&bundle).map(|_| {
-            println!("Bundle OK.");
-        }),
+        Command::VerifyBundle {
+            bundle,
+            signer,
+            audit_keys,
+        } => {
+            let keys = load_audit_keys(audit_keys.as_ref())?;
+            verify_bundle_with_keys(&bundle, &keys)?;
+            match (verify_manifest_signature(&bundle)?, signer) {
+                (Some(fingerprint), Some(expected)) if fingerprint != expected => Err(format!(
+                    "Manifest signed by {fingerprint}, expected {expected}"
+                )),
+                (None, Some(_)) => Err("Bundle is not signed".into()),
+                (Some(fingerprint), _) => {
+                    println!("Bundle OK. Manifest signed by {fingerprint}.");
+                    Ok(())
+                }
+                (None, None) => {
+                    println!("Bundle OK.");
+                    Ok(())
+                }
+            }
+        }
+        Command::VerifyChain { bundle, audit_keys } => {
+            let keys = load_audit_keys(audit_keys.as_ref())?;
+            verify_chain(&bundle, &keys).map(|head| {
+                println!("Audit chain OK. Head hash: {head}");
+            })
+        }
+        Command::VerifyAgainstVault {
+            manifest,
+            vault_url,
+            token,
+        } => {
+            let token = token
+                .or_else(|| std::env::var("VAULT_TOKEN").ok())
+                .filter(|token| !token.trim().is_empty())
+                .ok_or("A vault token is required (--token or VAULT_TOKEN)")?;
+            let client = reqwest::blocking::Client::new();
+            let checks = verify_against_vault(&manifest, |document_id, version_id| {
+                fetch_vault_sha256(&client, &vault_url, &token, document_id, version_id)
+            })?;
+            report_vault_checks(&checks)
+        }
     }
 }
 
@@ -83,6 +236,7 @@ mod tests {
             command: Command::VerifyAudit {
                 input: path,
                 head_hash: None,
+                audit_keys: None,
             },
         };
         run(args).expect("verify audit");
@@ -128,7 +282,11 @@ mod tests {
         fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
 
         let args = Args {
-            command: Command::VerifyBundle { bundle: dir },
+            command: Command::VerifyBundle {
+                bundle: dir,
+                signer: None,
+                audit_keys: None,
+            },
         };
         run(args).expect("verify bundle");
     }
@@ -144,8 +302,94 @@ mod tests {
             command: Command::VerifyAudit {
                 input: path,
                 head_hash: Some("bad".to_string()),
+                audit_keys: None,
             },
         };
         assert!(run(args).is_err());
     }
+
+    #[test]
+    fn run_verify_bundle_requires_signature_when_signer_pinned() {
+        let dir = unique_dir("audit-cli");
+        fs::create_dir_all(&dir).unwrap();
+        let manifest = serde_json::json!({
+            "case_id": "case-1",
+            "case_type": "mhca39",
+            "exported_at": "2025-01-01T00:00:00Z",
+            "audit_head_hash": "",
+            "audit_events_sha256": "a".repeat(64),
+            "documents": []
+        });
+        fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let args = Args {
+            command: Command::VerifyBundle {
+                bundle: dir,
+                signer: Some("f".repeat(64)),
+                audit_keys: None,
+            },
+        };
+        assert_eq!(run(args).unwrap_err(), "Bundle is not signed");
+    }
+
+    #[test]
+    fn load_audit_keys_reads_newline_separated_file() {
+        let dir = unique_dir("audit-cli");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit-keys");
+        fs::write(
+            &path,
+            format!("k1:{}\nk2:{}\n", "11".repeat(32), "22".repeat(32)),
+        )
+        .unwrap();
+
+        let keys = load_audit_keys(Some(&path)).expect("keys");
+        assert!(keys.get("k1").is_some());
+        assert!(keys.get("k2").is_some());
+        assert!(load_audit_keys(Some(&dir.join("missing"))).is_err());
+    }
+
+    #[test]
+    fn report_vault_checks_fails_on_any_mismatch() {
+        let check = |status| VaultCheck {
+            document_id: "doc-1".into(),
+            version_id: Some("v-1".into()),
+            bundle_path: "documents/a".into(),
+            status,
+        };
+        assert!(report_vault_checks(&[check(VaultCheckStatus::Match)]).is_ok());
+        assert!(report_vault_checks(&[check(VaultCheckStatus::Skipped)]).is_ok());
+        let error = report_vault_checks(&[
+            check(VaultCheckStatus::Match),
+            check(VaultCheckStatus::Unavailable {
+                error: "vault returned 404".into(),
+            }),
+        ])
+        .unwrap_err();
+        assert_eq!(error, "1 of 2 documents do not match the vault");
+    }
+
+    #[test]
+    fn run_verify_chain_reports_tampered_fixture() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let valid = Args {
+            command: Command::VerifyChain {
+                bundle: fixtures.join("chain-valid"),
+                audit_keys: None,
+            },
+        };
+        run(valid).expect("valid chain");
+
+        let tampered = Args {
+            command: Command::VerifyChain {
+                bundle: fixtures.join("chain-tampered"),
+                audit_keys: None,
+            },
+        };
+        assert_eq!(run(tampered).unwrap_err(), "Hash mismatch at line 2");
+    }
 }
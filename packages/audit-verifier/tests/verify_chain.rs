@@ -0,0 +1,64 @@
+use audit_verifier::{AuditKeyring, verify_bundle, verify_chain};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn copy_fixture(name: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("{name}-{}-{}", std::process::id(), nanos));
+    fs::create_dir_all(&dir).unwrap();
+    for file in ["manifest.json", "audit.jsonl"] {
+        fs::copy(fixture(name).join(file), dir.join(file)).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn valid_chain_verifies_to_the_manifest_head() {
+    let head = verify_chain(&fixture("chain-valid"), &AuditKeyring::default()).expect("chain");
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(fixture("chain-valid").join("manifest.json")).unwrap())
+            .unwrap();
+    assert_eq!(head, manifest["audit_head_hash"]);
+    verify_bundle(&fixture("chain-valid")).expect("bundle");
+}
+
+#[test]
+fn tampered_event_is_reported_at_its_line() {
+    // The manifest checksum was updated along with the edit, so only the chain walk
+    // can catch it.
+    let err = verify_chain(&fixture("chain-tampered"), &AuditKeyring::default()).unwrap_err();
+    assert_eq!(err, "Hash mismatch at line 2");
+}
+
+#[test]
+fn chain_rejects_checksum_and_head_mismatches() {
+    let dir = copy_fixture("chain-valid");
+    let manifest_path = dir.join("manifest.json");
+    let mut manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+
+    manifest["audit_head_hash"] = serde_json::json!("0".repeat(64));
+    fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+    let err = verify_chain(&dir, &AuditKeyring::default()).unwrap_err();
+    assert!(err.starts_with("Head hash mismatch at line 3"), "{err}");
+
+    let audit = fs::read_to_string(dir.join("audit.jsonl")).unwrap();
+    let truncated: Vec<&str> = audit.lines().take(2).collect();
+    fs::write(dir.join("audit.jsonl"), truncated.join("\n")).unwrap();
+    let err = verify_chain(&dir, &AuditKeyring::default()).unwrap_err();
+    assert_eq!(err, "audit.jsonl checksum mismatch");
+
+    fs::remove_file(dir.join("audit.jsonl")).unwrap();
+    let err = verify_chain(&dir, &AuditKeyring::default()).unwrap_err();
+    assert_eq!(err, "audit.jsonl missing from bundle");
+}
@@ -1,6 +1,9 @@
-use chrono::Utc;
+use chrono::{DateTime, SubsecRound, Utc};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
@@ -145,6 +148,330 @@ pub fn zero_hash() -> String {
     "0".repeat(64)
 }
 
+/// Shortest accepted HMAC secret for an audit key epoch, in bytes.
+pub const MIN_AUDIT_KEY_BYTES: usize = 32;
+
+/// HMAC secrets for audit key epochs, by key id. Secrets never live in the database; only
+/// the `key_epochs` table records which id is active, so forging a chain needs the key
+/// material and not just knowledge of the hashing algorithm.
+#[derive(Clone, Default)]
+pub struct AuditKeyring {
+    keys: HashMap<String, Vec<u8>>,
+    activated_at: HashMap<String, DateTime<Utc>>,
+}
+
+impl std::fmt::Debug for AuditKeyring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut key_ids: Vec<&String> = self.keys.keys().collect();
+        key_ids.sort();
+        f.debug_struct("AuditKeyring")
+            .field("key_ids", &key_ids)
+            .finish()
+    }
+}
+
+impl AuditKeyring {
+    /// Parses comma-separated `key_id:hex_secret` pairs, e.g. `2025a:9f…,2025b:41…`. An
+    /// entry may end in `@<RFC 3339>` giving the epoch's `activated_at`, which verifiers
+    /// use to refuse unkeyed events written after the first epoch.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut keyring = Self::default();
+        for entry in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (key_id, secret) = entry
+                .split_once(':')
+                .ok_or_else(|| format!("audit key entry '{entry}' must be key_id:hex_secret"))?;
+            let key_id = key_id.trim();
+            if key_id.is_empty() {
+                return Err("audit key id must not be empty".to_string());
+            }
+            let (secret, activated_at) = match secret.split_once('@') {
+                Some((secret, activated_at)) => {
+                    let activated_at =
+                        DateTime::parse_from_rfc3339(activated_at.trim()).map_err(|_| {
+                            format!("audit key '{key_id}' activation must be an RFC 3339 time")
+                        })?;
+                    (secret, Some(activated_at.with_timezone(&Utc)))
+                }
+                None => (secret, None),
+            };
+            let secret = hex::decode(secret.trim())
+                .map_err(|_| format!("audit key '{key_id}' secret must be hex"))?;
+            if secret.len() < MIN_AUDIT_KEY_BYTES {
+                return Err(format!(
+                    "audit key '{key_id}' secret must be at least {MIN_AUDIT_KEY_BYTES} bytes"
+                ));
+            }
+            if keyring.keys.insert(key_id.to_string(), secret).is_some() {
+                return Err(format!("audit key '{key_id}' is listed twice"));
+            }
+            if let Some(activated_at) = activated_at {
+                keyring
+                    .activated_at
+                    .insert(key_id.to_string(), activated_at);
+            }
+        }
+        Ok(keyring)
+    }
+
+    /// Loads `AUDIT_HMAC_KEYS`; unset or empty yields an empty keyring, which can only
+    /// append while no key epoch is active.
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("AUDIT_HMAC_KEYS") {
+            Ok(spec) => Self::parse(&spec),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn get(&self, key_id: &str) -> Option<&[u8]> {
+        self.keys.get(key_id).map(Vec::as_slice)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Earliest configured epoch activation. Every event created at or after it must be
+    /// keyed; `None` when no entry carries an activation time.
+    pub fn keyed_since(&self) -> Option<DateTime<Utc>> {
+        self.activated_at.values().min().copied()
+    }
+}
+
+/// `event_hash` for one link of the chain over `prev_hash || canonical_json`: plain
+/// SHA-256 for events written before key epochs (no key), HMAC-SHA256 under the
+/// epoch's secret otherwise.
+pub fn chain_hash(prev_hash: &str, canonical_json: &str, key: Option<&[u8]>) -> String {
+    match key {
+        Some(key) => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+            mac.update(prev_hash.as_bytes());
+            mac.update(canonical_json.as_bytes());
+            hex::encode(mac.finalize().into_bytes())
+        }
+        None => {
+            let mut hasher = Sha256::new();
+            hasher.update(prev_hash.as_bytes());
+            hasher.update(canonical_json.as_bytes());
+            hex::encode(hasher.finalize())
+        }
+    }
+}
+
+/// Canonical JSON text hashed into the audit chain: object keys sorted by their UTF-8
+/// bytes at every depth, no insignificant whitespace, strings escaped and numbers
+/// formatted exactly as `serde_json` writes them. The output does not depend on how the
+/// `Value` was built or on `serde_json`'s map ordering, so the appending services and the
+/// verifier always hash the same bytes for the same event.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            out.push('{');
+            for (index, (key, inner)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_json_string(key, out);
+                out.push(':');
+                write_canonical(inner, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (index, inner) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(inner, out);
+            }
+            out.push(']');
+        }
+        Value::String(text) => write_json_string(text, out),
+        Value::Number(number) => out.push_str(&number.to_string()),
+        Value::Bool(flag) => out.push_str(if *flag { "true" } else { "false" }),
+        Value::Null => out.push_str("null"),
+    }
+}
+
+fn write_json_string(text: &str, out: &mut String) {
+    out.push_str(&serde_json::to_string(text).expect("strings always serialize"));
+}
+
+/// Advisory lock key that serialises appends and key rotations on the single
+/// `audit_events` chain. Every appending service takes the same lock, so two mutations
+/// can never read the same head and fork the chain.
+pub const AUDIT_CHAIN_LOCK_KEY: i64 = 0x6c72_6175_6469_7400;
+
+/// Takes the chain lock for the rest of `tx`.
+pub async fn lock_audit_chain(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(AUDIT_CHAIN_LOCK_KEY)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// Active audit key epoch (newest unretired `key_epochs` row) and its secret. Fails
+/// closed when the active key id is missing from the keyring rather than silently
+/// falling back to an unkeyed hash.
+pub async fn active_audit_epoch<'k>(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    audit_keys: &'k AuditKeyring,
+) -> Result<Option<(String, &'k [u8])>, sqlx::Error> {
+    let key_id: Option<String> = sqlx::query_scalar(
+        "SELECT key_id FROM key_epochs WHERE retired_at IS NULL ORDER BY activated_at DESC LIMIT 1",
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    let Some(key_id) = key_id else {
+        return Ok(None);
+    };
+    let key = audit_keys.get(&key_id).ok_or_else(|| {
+        sqlx::Error::Configuration(
+            format!("active audit key epoch '{key_id}' is not in AUDIT_HMAC_KEYS").into(),
+        )
+    })?;
+    Ok(Some((key_id, key)))
+}
+
+/// What a service asks [`append_chained_event`] to record.
+#[derive(Debug, Clone)]
+pub struct ChainAppend<'a> {
+    pub actor_principal_id: Uuid,
+    pub action: &'a str,
+    pub tier: &'a str,
+    pub case_id: Option<Uuid>,
+    pub payload: Value,
+}
+
+/// One link as written to `audit_events`.
+#[derive(Debug, Clone)]
+pub struct ChainedEvent {
+    pub event_id: Uuid,
+    /// Truncated to microseconds, so the hashed timestamp survives `timestamptz`.
+    pub created_at: DateTime<Utc>,
+    pub prev_hash: String,
+    pub event_hash: String,
+    /// Key epoch whose secret produced `event_hash`; `None` before key epochs.
+    pub key_id: Option<String>,
+    pub payload: Value,
+}
+
+/// The event fields hashed into the chain, as canonical JSON.
+pub fn canonical_chain_event(
+    event_id: &str,
+    created_at: &str,
+    actor_principal_id: &str,
+    action: &str,
+    tier: &str,
+    case_id: Option<&str>,
+    payload: &Value,
+) -> String {
+    canonical_json(&serde_json::json!({
+        "event_id": event_id,
+        "created_at": created_at,
+        "actor_principal_id": actor_principal_id,
+        "action": action,
+        "tier": tier,
+        "case_id": case_id,
+        "payload": payload,
+    }))
+}
+
+/// Appends one event to `audit_events` inside `tx`, chained to the current head and
+/// keyed under the active epoch. The caller's mutation and its audit record commit or
+/// roll back together.
+pub async fn append_chained_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    audit_keys: &AuditKeyring,
+    event: ChainAppend<'_>,
+) -> Result<ChainedEvent, sqlx::Error> {
+    lock_audit_chain(tx).await?;
+    let prev_hash: String =
+        sqlx::query_scalar("SELECT event_hash FROM audit_events ORDER BY created_at DESC LIMIT 1")
+            .fetch_optional(&mut **tx)
+            .await?
+            .unwrap_or_else(zero_hash);
+    let epoch = active_audit_epoch(tx, audit_keys).await?;
+
+    let event_id = Uuid::new_v4();
+    let created_at = Utc::now().trunc_subsecs(6);
+    let case_id = event.case_id.map(|id| id.to_string());
+    let event_hash = chain_hash(
+        &prev_hash,
+        &canonical_chain_event(
+            &event_id.to_string(),
+            &created_at.to_rfc3339(),
+            &event.actor_principal_id.to_string(),
+            event.action,
+            event.tier,
+            case_id.as_deref(),
+            &event.payload,
+        ),
+        epoch.as_ref().map(|(_, key)| *key),
+    );
+    let key_id = epoch.map(|(key_id, _)| key_id);
+
+    sqlx::query(
+        "INSERT INTO audit_events (event_id, created_at, actor_principal_id, action, tier, case_id, payload, prev_hash, event_hash, key_id) \
+         VALUES ($1, $2, $3, $4, $5::sensitivity_tier, $6, $7, $8, $9, $10)",
+    )
+    .bind(event_id)
+    .bind(created_at)
+    .bind(event.actor_principal_id)
+    .bind(event.action)
+    .bind(event.tier)
+    .bind(event.case_id)
+    .bind(&event.payload)
+    .bind(&prev_hash)
+    .bind(&event_hash)
+    .bind(&key_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(ChainedEvent {
+        event_id,
+        created_at,
+        prev_hash,
+        event_hash,
+        key_id,
+        payload: event.payload,
+    })
+}
+
+/// Digest a case export records as `export_binding_sha256` (manifest schema_version 4+),
+/// committing to the audit head, the `audit.jsonl` checksum and every `(sha256,
+/// bundle_path)` document entry at once. Any of them swapped without the others, say a
+/// different but internally valid `audit.jsonl`, no longer reproduces the digest, and a
+/// manifest signature over it covers all three.
+pub fn export_binding_sha256<'a>(
+    audit_head_hash: &str,
+    audit_events_sha256: &str,
+    documents: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("audit_head_hash:{audit_head_hash}\n"));
+    hasher.update(format!("audit_events_sha256:{audit_events_sha256}\n"));
+    for (sha256, bundle_path) in documents {
+        hasher.update(format!("document:{sha256}  {bundle_path}\n"));
+    }
+    hex::encode(hasher.finalize())
+}
+
 impl AuditClient for InMemoryAuditSink {
     fn record(&self, event: AuditEvent) -> AuditResult<()> {
         InMemoryAuditSink::record(self, event);
@@ -214,4 +541,124 @@ mod tests {
         assert_eq!(h.len(), 64);
         assert!(h.chars().all(|c| c == '0'));
     }
+
+    #[test]
+    fn audit_keyring_parses_pairs_and_rejects_bad_entries() {
+        let secret = "ab".repeat(MIN_AUDIT_KEY_BYTES);
+        let keyring = AuditKeyring::parse(&format!("2025a:{secret}, 2025b:{secret}")).unwrap();
+        assert_eq!(
+            keyring.get("2025a").map(<[u8]>::len),
+            Some(MIN_AUDIT_KEY_BYTES)
+        );
+        assert!(keyring.get("2025c").is_none());
+        assert!(!format!("{keyring:?}").contains(&secret));
+        assert_eq!(keyring.keyed_since(), None);
+
+        let dated = AuditKeyring::parse(&format!(
+            "2025a:{secret}@2025-03-01T00:00:00Z,2025b:{secret}@2025-09-01T00:00:00Z"
+        ))
+        .unwrap();
+        assert!(dated.get("2025a").is_some());
+        assert_eq!(
+            dated.keyed_since().map(|at| at.to_rfc3339()),
+            Some("2025-03-01T00:00:00+00:00".to_string())
+        );
+
+        assert!(AuditKeyring::parse("").unwrap().is_empty());
+        assert!(AuditKeyring::parse("no-separator").is_err());
+        assert!(AuditKeyring::parse(&format!(":{secret}")).is_err());
+        assert!(AuditKeyring::parse("short:abcd").is_err());
+        assert!(AuditKeyring::parse("bad:zz").is_err());
+        assert!(AuditKeyring::parse(&format!("k:{secret},k:{secret}")).is_err());
+        assert!(AuditKeyring::parse(&format!("k:{secret}@yesterday")).is_err());
+    }
+
+    #[test]
+    fn export_binding_sha256_changes_with_each_input() {
+        let head = "a".repeat(64);
+        let audit = "b".repeat(64);
+        let doc = "c".repeat(64);
+        let base = export_binding_sha256(&head, &audit, [(doc.as_str(), "documents/1")]);
+        assert_eq!(base.len(), 64);
+        assert_eq!(
+            base,
+            export_binding_sha256(&head, &audit, [(doc.as_str(), "documents/1")])
+        );
+        assert_ne!(
+            base,
+            export_binding_sha256(&zero_hash(), &audit, [(doc.as_str(), "documents/1")])
+        );
+        assert_ne!(
+            base,
+            export_binding_sha256(&head, &zero_hash(), [(doc.as_str(), "documents/1")])
+        );
+        assert_ne!(
+            base,
+            export_binding_sha256(&head, &audit, [(doc.as_str(), "documents/2")])
+        );
+        assert_ne!(base, export_binding_sha256(&head, &audit, []));
+    }
+
+    #[test]
+    fn chain_hash_is_keyed_only_when_a_key_is_given() {
+        let prev = zero_hash();
+        let plain = chain_hash(&prev, "{}", None);
+        let mut hasher = Sha256::new();
+        hasher.update(prev.as_bytes());
+        hasher.update(b"{}");
+        assert_eq!(plain, hex::encode(hasher.finalize()));
+
+        let keyed = chain_hash(&prev, "{}", Some(&[7; 32]));
+        assert_eq!(keyed.len(), 64);
+        assert_ne!(keyed, plain);
+        assert_ne!(keyed, chain_hash(&prev, "{}", Some(&[8; 32])));
+    }
+
+    #[test]
+    fn canonical_json_sorts_keys_and_drops_whitespace() {
+        let value: Value = serde_json::from_str(
+            r#"{ "b": 1, "a": { "d": [1.5, {"z": null, "y": true}], "c": "x\"y\u00e9" }, "A": 1e2 }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"A":100.0,"a":{"c":"x\"yé","d":[1.5,{"y":true,"z":null}]},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn canonical_json_is_stable_and_is_what_the_chain_hashes() {
+        let forward = serde_json::json!({
+            "event_id": "e-1",
+            "created_at": "2025-06-01T00:00:00+00:00",
+            "actor_principal_id": "p-1",
+            "action": "case.created",
+            "tier": "amber",
+            "case_id": null,
+            "payload": {"size": 1024, "ratio": 0.25, "tags": ["b", "a"]},
+        });
+        let mut reversed = serde_json::Map::new();
+        let object = forward.as_object().unwrap();
+        for key in object.keys().rev() {
+            reversed.insert(key.clone(), object[key].clone());
+        }
+        let reversed = Value::Object(reversed);
+
+        let canonical = canonical_json(&forward);
+        assert_eq!(canonical, canonical_json(&reversed));
+        assert_eq!(
+            canonical,
+            canonical_json(&serde_json::from_str(&canonical).unwrap())
+        );
+        assert!(canonical.starts_with(r#"{"action":"case.created","actor_principal_id":"p-1""#));
+
+        let prev = zero_hash();
+        let mut hasher = Sha256::new();
+        hasher.update(prev.as_bytes());
+        hasher.update(canonical.as_bytes());
+        assert_eq!(
+            chain_hash(&prev, &canonical, None),
+            hex::encode(hasher.finalize())
+        );
+    }
 }
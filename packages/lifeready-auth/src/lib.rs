@@ -7,16 +7,20 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use chrono::{Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sha2::Sha256;
 use std::{
     future::Future,
+    net::SocketAddr,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
 };
 use tower::{Layer, Service};
+use tracing::Instrument;
 use uuid::Uuid;
 
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
@@ -47,7 +51,10 @@ impl LifereadyEnv {
 type AxumRequest = Request<Body>;
 type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+/// Ordered by sensitivity: `Green < Amber < Red`.
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, utoipa::ToSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum SensitivityTier {
     Green,
@@ -55,13 +62,14 @@ pub enum SensitivityTier {
     Red,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Role {
     Principal,
     Proxy,
     ExecutorNominee,
     EmergencyContact,
+    Administrator,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
@@ -89,6 +97,16 @@ pub struct Claims {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aud: Option<String>,
     pub email: Option<String>,
+    /// Principal a proxy is acting for. Services only honour it alongside a matching
+    /// proxy authorization; on its own it grants nothing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acting_for: Option<String>,
+    /// Tenant the principal belongs to. Absent for single-tenant deployments. The claim
+    /// comes from whichever issuer signs tokens for a multi-tenant deployment; the bundled
+    /// identity-service has no tenant directory, never sets it at sign-in and only carries
+    /// an existing value through refresh.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
 }
 
 impl Claims {
@@ -116,14 +134,30 @@ impl Claims {
             iss: None,
             aud: None,
             email,
+            acting_for: None,
+            org_id: None,
         }
     }
 }
 
+/// Access token plus the refresh token that can later be exchanged for a new one.
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub refresh_token_id: Uuid,
+}
+
+/// Domain-separated so a refresh token signature can never double as a signed URL.
+fn refresh_token_message(token_id: Uuid) -> String {
+    format!("refresh:{token_id}")
+}
+
 #[derive(Clone)]
 pub struct AuthConfig {
     encoding_key: Arc<EncodingKey>,
     decoding_key: Arc<DecodingKey>,
+    signing_key: Arc<Vec<u8>>,
     issuer: Option<String>,
     audience: Option<String>,
     leeway_seconds: u64,
@@ -134,6 +168,7 @@ impl std::fmt::Debug for AuthConfig {
         f.debug_struct("AuthConfig")
             .field("encoding_key", &"<redacted>")
             .field("decoding_key", &"<redacted>")
+            .field("signing_key", &"<redacted>")
             .field("issuer", &self.issuer)
             .field("audience", &self.audience)
             .field("leeway_seconds", &self.leeway_seconds)
@@ -149,6 +184,7 @@ impl AuthConfig {
         Self {
             encoding_key,
             decoding_key,
+            signing_key: Arc::new(secret.into_bytes()),
             issuer: None,
             audience: None,
             leeway_seconds: 30,
@@ -243,16 +279,72 @@ impl AuthConfig {
         Ok(data.claims)
     }
 
+    /// Hex-encoded HMAC-SHA256 of `message` keyed by the JWT secret, for stateless
+    /// signed URLs that any replica can verify.
+    pub fn sign(&self, message: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(message.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Constant-time check of a signature produced by [`AuthConfig::sign`].
+    pub fn verify_signature(&self, message: &str, signature: &str) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(message.as_bytes());
+        mac.verify_slice(&signature).is_ok()
+    }
+
+    /// Mint an access token for `claims` alongside a fresh refresh token.
+    ///
+    /// The refresh token is opaque to clients: a random id plus an HMAC over it, so a
+    /// service can reject forgeries before touching storage. Rotation and revocation are
+    /// tracked by whoever persists [`TokenPair::refresh_token_id`].
+    pub fn issue_token_pair(&self, claims: &Claims) -> Result<TokenPair, AuthError> {
+        let access_token = self.issue_token(claims)?;
+        let refresh_token_id = Uuid::new_v4();
+        Ok(TokenPair {
+            access_token,
+            refresh_token: self.refresh_token_for(refresh_token_id),
+            refresh_token_id,
+        })
+    }
+
+    /// Opaque refresh token for `token_id`.
+    pub fn refresh_token_for(&self, token_id: Uuid) -> String {
+        format!("{token_id}.{}", self.sign(&refresh_token_message(token_id)))
+    }
+
+    /// The id inside a refresh token minted by this config, or `None` for anything that
+    /// is malformed or was signed with another secret.
+    pub fn refresh_token_id(&self, refresh_token: &str) -> Option<Uuid> {
+        let (token_id, signature) = refresh_token.split_once('.')?;
+        let token_id = Uuid::parse_str(token_id).ok()?;
+        self.verify_signature(&refresh_token_message(token_id), signature)
+            .then_some(token_id)
+    }
+
+    /// A configured issuer or audience is also a required claim: jsonwebtoken only checks
+    /// `iss`/`aud` when the token carries them, so a token minted without them would
+    /// otherwise pass for any service.
     fn validation(&self) -> Validation {
         let mut validation = Validation::new(Algorithm::HS256);
         validation.leeway = self.leeway_seconds;
 
+        let mut required = vec!["exp"];
         if let Some(issuer) = &self.issuer {
             validation.set_issuer(&[issuer]);
+            required.push("iss");
         }
         if let Some(audience) = &self.audience {
             validation.set_audience(&[audience]);
+            required.push("aud");
         }
+        validation.set_required_spec_claims(&required);
 
         validation
     }
@@ -280,6 +372,8 @@ pub struct RequestContext {
     pub scopes: Vec<String>,
     pub expires_at: chrono::DateTime<Utc>,
     pub email: Option<String>,
+    pub acting_for: Option<String>,
+    pub org_id: Option<String>,
 }
 
 impl RequestContext {
@@ -303,6 +397,8 @@ impl RequestContext {
             scopes,
             expires_at,
             email: claims.email.clone(),
+            acting_for: claims.acting_for.clone(),
+            org_id: claims.org_id.clone(),
         }
     }
 }
@@ -329,6 +425,129 @@ where
     }
 }
 
+/// `Json<T>` that rejects with the API's problem+json bodies instead of axum's plain-text
+/// rejections: a body that fails to deserialize becomes a `400 invalid_request` naming the
+/// offending field, tagged with the request id like every hand-written validation error.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonBody<T>(pub T);
+
+impl<T, S> axum::extract::FromRequest<S> for JsonBody<T>
+where
+    T: serde::de::DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: AxumRequest, state: &S) -> Result<Self, Self::Rejection> {
+        let request_id = req.extensions().get::<RequestId>().copied();
+        if !json_content_type(req.headers()) {
+            return Err(unsupported_json_content_type(request_id));
+        }
+        json_body(req, state, request_id).await.map(JsonBody)
+    }
+}
+
+/// An absent body (no `Content-Type`) is `None`; anything else must be valid JSON.
+impl<T, S> axum::extract::OptionalFromRequest<S> for JsonBody<T>
+where
+    T: serde::de::DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: AxumRequest, state: &S) -> Result<Option<Self>, Self::Rejection> {
+        let request_id = req.extensions().get::<RequestId>().copied();
+        if !req.headers().contains_key(header::CONTENT_TYPE) {
+            return Ok(None);
+        }
+        if !json_content_type(req.headers()) {
+            return Err(unsupported_json_content_type(request_id));
+        }
+        json_body(req, state, request_id)
+            .await
+            .map(|value| Some(JsonBody(value)))
+    }
+}
+
+fn json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json"
+        || essence
+            .strip_prefix("application/")
+            .is_some_and(|subtype| subtype.ends_with("+json"))
+}
+
+fn unsupported_json_content_type(request_id: Option<RequestId>) -> Response {
+    unsupported_media_type(request_id, "expected Content-Type: application/json")
+}
+
+async fn json_body<T, S>(
+    req: AxumRequest,
+    state: &S,
+    request_id: Option<RequestId>,
+) -> Result<T, Response>
+where
+    T: serde::de::DeserializeOwned,
+    S: Send + Sync,
+{
+    use axum::extract::FromRequest;
+
+    let bytes = axum::body::Bytes::from_request(req, state)
+        .await
+        .map_err(|rejection| {
+            let status = rejection.status();
+            if status == StatusCode::PAYLOAD_TOO_LARGE {
+                return payload_too_large(request_id);
+            }
+            problem_response(
+                status,
+                "https://errors.lifeready.local/request/invalid",
+                status.canonical_reason().unwrap_or("Invalid request"),
+                "invalid_request",
+                Some(rejection.body_text()),
+                request_id.map(|id| id.0),
+            )
+        })?;
+    parse_json_body(&bytes).map_err(|detail| invalid_request(request_id, detail))
+}
+
+/// Deserializes a request body, describing failures as `"<field path>: <reason>"` without
+/// serde_json's line/column suffix, e.g. `invalid request body: missing field `title``.
+fn parse_json_body<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|error| {
+        let path = error.path().to_string();
+        let error = error.into_inner();
+        if !error.is_data() {
+            return format!("malformed JSON body: {error}");
+        }
+        let message = error.to_string();
+        let reason = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(reason, _)| reason);
+        if path == "." {
+            format!("invalid request body: {reason}")
+        } else {
+            format!("invalid request body: {path}: {reason}")
+        }
+    })?;
+    deserializer
+        .end()
+        .map_err(|error| format!("malformed JSON body: {error}"))?;
+    Ok(value)
+}
+
 #[derive(Debug, Clone)]
 pub enum AuthError {
     Unauthorized { detail: String },
@@ -363,34 +582,45 @@ impl AuthError {
     }
 
     pub fn into_response(self, request_id: Option<RequestId>) -> Response {
-        let (status, title, r#type, detail) = match self {
+        let (status, title, r#type, code, detail) = match self {
             AuthError::Unauthorized { detail } => (
                 StatusCode::UNAUTHORIZED,
                 "Unauthorized",
                 "https://errors.lifeready.local/auth/unauthorized",
+                "unauthorized",
                 Some(detail),
             ),
             AuthError::Forbidden { detail } => (
                 StatusCode::FORBIDDEN,
                 "Forbidden",
                 "https://errors.lifeready.local/auth/forbidden",
+                "forbidden",
                 Some(detail),
             ),
             AuthError::Invalid { detail } => (
                 StatusCode::BAD_REQUEST,
                 "Invalid request",
                 "https://errors.lifeready.local/request/invalid",
+                "invalid_request",
                 Some(detail),
             ),
             AuthError::Misconfigured { detail } => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Service misconfigured",
                 "https://errors.lifeready.local/config/misconfigured",
+                "misconfigured",
                 Some(detail),
             ),
         };
 
-        problem_response(status, r#type, title, detail, request_id.map(|id| id.0))
+        problem_response(
+            status,
+            r#type,
+            title,
+            code,
+            detail,
+            request_id.map(|id| id.0),
+        )
     }
 }
 
@@ -413,12 +643,15 @@ impl std::fmt::Display for AuthError {
 
 impl std::error::Error for AuthError {}
 
-#[derive(Debug, Serialize)]
+/// RFC 7807 body. `code` is a stable snake_case identifier clients can branch on
+/// instead of matching `detail`, which is human-readable and may change.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
 struct ProblemDetails {
     #[serde(rename = "type")]
     r#type: String,
     title: String,
     status: u16,
+    code: String,
     detail: Option<String>,
     instance: Option<String>,
     request_id: Option<Uuid>,
@@ -429,6 +662,7 @@ fn problem_response(
     status: StatusCode,
     r#type: &str,
     title: &str,
+    code: &str,
     detail: Option<String>,
     request_id: Option<Uuid>,
 ) -> Response {
@@ -436,8 +670,9 @@ fn problem_response(
         r#type: r#type.to_string(),
         title: title.to_string(),
         status: status.as_u16(),
+        code: code.to_string(),
         detail,
-        instance: None,
+        instance: request_id.map(|id| format!("urn:uuid:{id}")),
         request_id,
         errors: None,
     };
@@ -450,6 +685,149 @@ fn problem_response(
     response
 }
 
+/// Path every service serves its generated OpenAPI document on. Like the health probes
+/// it bypasses authentication so clients can discover the API before holding a token.
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Path services serve Prometheus counters on. Scrapers hold no token, so it bypasses
+/// authentication; the counters carry no identifiers.
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Prometheus text exposition format.
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// The problem+json responses `common.openapi.yaml` names, with their descriptions.
+const PROBLEM_RESPONSES: &[(&str, &str)] = &[
+    ("ProblemDetailsResponse", "Error response"),
+    ("Unauthorized", "Unauthorized"),
+    ("Forbidden", "Forbidden"),
+    ("NotFound", "Not Found"),
+    ("Conflict", "Conflict"),
+    ("Gone", "Gone"),
+    (
+        "PreconditionFailed",
+        "Conditional request precondition (If-Match) not met",
+    ),
+    ("PayloadTooLarge", "Request body exceeds the size limit"),
+    (
+        "RangeNotSatisfiable",
+        "Requested byte range lies outside the content",
+    ),
+    (
+        "TooManyRequests",
+        "Write rate limit for this principal exhausted",
+    ),
+    ("ServerError", "Internal Server Error"),
+];
+
+fn problem_response_doc(name: &str) -> utoipa::openapi::Response {
+    use utoipa::openapi::{ContentBuilder, Ref, ResponseBuilder};
+
+    let description = PROBLEM_RESPONSES
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map_or(name, |(_, description)| description);
+    ResponseBuilder::new()
+        .description(description)
+        .content(
+            "application/problem+json",
+            ContentBuilder::new()
+                .schema(Some(Ref::from_schema_name("ProblemDetails")))
+                .build(),
+        )
+        .build()
+}
+
+/// Adds what every service's OpenAPI document shares: the `bearerAuth` JWT scheme as
+/// the default requirement, the `ProblemDetails` schema, and the problem+json error
+/// responses named as in `common.openapi.yaml`.
+pub struct OpenApiCommon;
+
+impl utoipa::Modify for OpenApiCommon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        use utoipa::openapi::SecurityRequirement;
+        use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+        use utoipa::{PartialSchema, ToSchema};
+
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearerAuth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.schemas.insert(
+            ProblemDetails::name().into_owned(),
+            ProblemDetails::schema(),
+        );
+        for (name, _) in PROBLEM_RESPONSES {
+            components
+                .responses
+                .insert(name.to_string(), problem_response_doc(name).into());
+        }
+        openapi.security = Some(vec![SecurityRequirement::new(
+            "bearerAuth",
+            Vec::<String>::new(),
+        )]);
+    }
+}
+
+/// The errors every authenticated route can answer with (400, 401, 403, 500), for the
+/// `responses(...)` list of a `#[utoipa::path]`. Each references the shared component
+/// [`OpenApiCommon`] registers.
+pub struct ProblemResponses;
+
+impl utoipa::IntoResponses for ProblemResponses {
+    fn responses() -> std::collections::BTreeMap<
+        String,
+        utoipa::openapi::RefOr<utoipa::openapi::response::Response>,
+    > {
+        [
+            ("400", "ProblemDetailsResponse"),
+            ("401", "Unauthorized"),
+            ("403", "Forbidden"),
+            ("500", "ServerError"),
+        ]
+        .into_iter()
+        .map(|(status, name)| {
+            (
+                status.to_string(),
+                utoipa::openapi::Ref::from_response_name(name).into(),
+            )
+        })
+        .collect()
+    }
+}
+
+macro_rules! problem_response_refs {
+    ($($marker:ident => $name:literal),* $(,)?) => {$(
+        #[doc = concat!("References the shared `", $name, "` response in a `#[utoipa::path]`, e.g. `(status = 404, response = NotFoundResponse)`.")]
+        pub struct $marker;
+
+        impl<'s> utoipa::ToResponse<'s> for $marker {
+            fn response() -> (
+                &'s str,
+                utoipa::openapi::RefOr<utoipa::openapi::response::Response>,
+            ) {
+                ($name, problem_response_doc($name).into())
+            }
+        }
+    )*};
+}
+
+problem_response_refs! {
+    NotFoundResponse => "NotFound",
+    ConflictResponse => "Conflict",
+    GoneResponse => "Gone",
+    PreconditionFailedResponse => "PreconditionFailed",
+    PayloadTooLargeResponse => "PayloadTooLarge",
+    RangeNotSatisfiableResponse => "RangeNotSatisfiable",
+    TooManyRequestsResponse => "TooManyRequests",
+}
+
 fn access_level_scope(level: AccessLevel) -> &'static str {
     match level {
         AccessLevel::ReadOnlyPacks => "read:packs",
@@ -462,6 +840,7 @@ fn access_level_scope(level: AccessLevel) -> &'static str {
 pub struct AuthLayerState {
     config: AuthConfig,
     allowlist: Vec<String>,
+    revoked: RevokedTokens,
 }
 
 impl AuthLayerState {
@@ -469,14 +848,175 @@ impl AuthLayerState {
         Self {
             config,
             allowlist: allowlist.into_iter().map(Into::into).collect(),
+            revoked: RevokedTokens::default(),
+        }
+    }
+
+    /// Reject tokens whose `jti` is in `revoked`.
+    pub fn with_revocations(mut self, revoked: RevokedTokens) -> Self {
+        self.revoked = revoked;
+        self
+    }
+}
+
+pub const DEFAULT_REVOCATION_REFRESH_SECS: u64 = 30;
+
+/// Reads `REVOCATION_REFRESH_SECS`, how stale a service's copy of the `revoked_tokens`
+/// table may get before the next authenticated request reloads it. Unset or zero falls
+/// back to [`DEFAULT_REVOCATION_REFRESH_SECS`].
+pub fn revocation_refresh_from_env() -> std::time::Duration {
+    let secs = std::env::var("REVOCATION_REFRESH_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_REVOCATION_REFRESH_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Token ids (`jti`) revoked before their `exp`, keyed to that `exp` so entries can be
+/// dropped once the token would have been rejected anyway.
+///
+/// When backed by a pool the first request waits for the set to load from
+/// `revoked_tokens`; after that it is reloaded in the background, triggered by the first
+/// request after each refresh interval. Until a load has succeeded, and again after one
+/// fails, requests wait on a fresh load and are refused if it fails too, so a revoked
+/// token is never accepted just because the table could not be read.
+#[derive(Clone)]
+pub struct RevokedTokens {
+    entries: Arc<dashmap::DashMap<String, i64>>,
+    pool: Option<sqlx::PgPool>,
+    refresh_every: std::time::Duration,
+    refresh: Arc<std::sync::Mutex<RevocationRefresh>>,
+}
+
+#[derive(Default)]
+struct RevocationRefresh {
+    started_at: Option<std::time::Instant>,
+    loaded: bool,
+}
+
+impl Default for RevokedTokens {
+    fn default() -> Self {
+        Self {
+            entries: Arc::new(dashmap::DashMap::new()),
+            pool: None,
+            refresh_every: std::time::Duration::from_secs(DEFAULT_REVOCATION_REFRESH_SECS),
+            refresh: Arc::new(std::sync::Mutex::new(RevocationRefresh::default())),
+        }
+    }
+}
+
+impl RevokedTokens {
+    pub fn backed_by(pool: sqlx::PgPool, refresh_every: std::time::Duration) -> Self {
+        Self {
+            pool: Some(pool),
+            refresh_every,
+            ..Self::default()
+        }
+    }
+
+    /// Pool-backed when `pool` is present, otherwise an in-memory set only this process sees.
+    pub fn from_pool(pool: Option<&sqlx::PgPool>) -> Self {
+        match pool {
+            Some(pool) => Self::backed_by(pool.clone(), revocation_refresh_from_env()),
+            None => Self::default(),
+        }
+    }
+
+    pub fn revoke(&self, jti: impl Into<String>, expires_at: chrono::DateTime<Utc>) {
+        self.entries.insert(jti.into(), expires_at.timestamp());
+    }
+
+    pub fn is_revoked(&self, jti: &str) -> bool {
+        self.entries
+            .get(jti)
+            .is_some_and(|expires_at| *expires_at > Utc::now().timestamp())
+    }
+
+    /// Forget entries whose token has expired on its own.
+    pub fn purge_expired(&self) {
+        let now = Utc::now().timestamp();
+        self.entries.retain(|_, expires_at| *expires_at > now);
+    }
+
+    /// Load every unexpired row of `revoked_tokens` into the set.
+    pub async fn reload(&self) -> Result<(), sqlx::Error> {
+        let Some(pool) = &self.pool else {
+            return Ok(());
+        };
+        let rows: Vec<(String, chrono::DateTime<Utc>)> =
+            sqlx::query_as("SELECT jti, expires_at FROM revoked_tokens WHERE expires_at > now()")
+                .fetch_all(pool)
+                .await?;
+        for (jti, expires_at) in rows {
+            self.revoke(jti, expires_at);
         }
+        self.purge_expired();
+        Ok(())
     }
+
+    /// Whether the set can be trusted for the next check. Once loaded, a stale set
+    /// starts a background reload and answers from the current entries; before the first
+    /// successful load, or after a failed one, the caller waits on a reload and gets
+    /// `false` if it fails.
+    async fn ensure_loaded(&self) -> bool {
+        if self.pool.is_none() {
+            return true;
+        }
+        let background = {
+            let mut refresh = self
+                .refresh
+                .lock()
+                .unwrap_or_else(|error| error.into_inner());
+            if !refresh.loaded {
+                false
+            } else if refresh
+                .started_at
+                .is_some_and(|at| at.elapsed() < self.refresh_every)
+            {
+                return true;
+            } else {
+                refresh.started_at = Some(std::time::Instant::now());
+                true
+            }
+        };
+        if background {
+            let revoked = self.clone();
+            tokio::spawn(async move { revoked.reload_and_record().await });
+            return true;
+        }
+        self.reload_and_record().await
+    }
+
+    async fn reload_and_record(&self) -> bool {
+        let started_at = std::time::Instant::now();
+        let result = self.reload().await;
+        if let Err(error) = &result {
+            tracing::warn!(error = %error, "revoked token reload failed; refusing tokens until it succeeds");
+        }
+        let mut refresh = self
+            .refresh
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        refresh.started_at = Some(started_at);
+        refresh.loaded = result.is_ok();
+        refresh.loaded
+    }
+}
+
+fn revocations_unavailable(request_id: RequestId) -> Response {
+    service_busy(
+        Some(request_id),
+        "token revocations could not be loaded",
+        std::time::Duration::from_secs(1),
+    )
 }
 
 #[derive(Clone)]
 pub struct AuthLayer {
     config: Arc<AuthConfig>,
     allowlist: Arc<Vec<String>>,
+    revoked: RevokedTokens,
 }
 
 impl AuthLayer {
@@ -484,9 +1024,16 @@ impl AuthLayer {
         Self {
             config,
             allowlist: Arc::new(Vec::new()),
+            revoked: RevokedTokens::default(),
         }
     }
 
+    /// Reject tokens whose `jti` is in `revoked`.
+    pub fn with_revocations(mut self, revoked: RevokedTokens) -> Self {
+        self.revoked = revoked;
+        self
+    }
+
     pub fn with_allowlist(
         mut self,
         allowlist: impl IntoIterator<Item = impl Into<String>>,
@@ -504,6 +1051,7 @@ impl<S> Layer<S> for AuthLayer {
             inner,
             config: self.config.clone(),
             allowlist: self.allowlist.clone(),
+            revoked: self.revoked.clone(),
         }
     }
 }
@@ -513,6 +1061,7 @@ pub struct AuthService<S> {
     inner: S,
     config: Arc<AuthConfig>,
     allowlist: Arc<Vec<String>>,
+    revoked: RevokedTokens,
 }
 
 impl<S, B> Service<Request<B>> for AuthService<S>
@@ -532,12 +1081,15 @@ where
     fn call(&mut self, mut req: Request<B>) -> Self::Future {
         let config = self.config.clone();
         let allowlist = self.allowlist.clone();
+        let revoked = self.revoked.clone();
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
             let path = req.uri().path();
             if path == "/healthz"
                 || path == "/readyz"
+                || path == OPENAPI_PATH
+                || path == METRICS_PATH
                 || allowlist.iter().any(|allowed| allowed == path)
             {
                 return inner.call(req).await;
@@ -560,7 +1112,16 @@ where
                 Err(error) => return Ok(error.into_response(Some(request_id))),
             };
 
+            if !revoked.ensure_loaded().await {
+                return Ok(revocations_unavailable(request_id));
+            }
+            if revoked.is_revoked(&claims.jti) {
+                return Ok(AuthError::unauthorized("token has been revoked")
+                    .into_response(Some(request_id)));
+            }
+
             let ctx = RequestContext::from_claims(request_id, &claims);
+            tracing::Span::current().record("principal_id", ctx.principal_id.as_str());
             req.extensions_mut().insert(claims);
             req.extensions_mut().insert(ctx);
 
@@ -569,106 +1130,769 @@ where
     }
 }
 
-pub async fn request_id_middleware(mut req: AxumRequest, next: Next) -> Response {
-    let request_id =
-        RequestId::from_headers(req.headers()).unwrap_or_else(|| RequestId(Uuid::new_v4()));
-    req.extensions_mut().insert(request_id);
+pub const DEFAULT_WRITE_RATE_PER_MIN: u32 = 60;
 
-    let mut response = next.run(req).await;
-    response.headers_mut().insert(
-        REQUEST_ID_HEADER,
-        HeaderValue::from_str(&request_id.0.to_string())
-            .unwrap_or_else(|_| HeaderValue::from_static("")),
-    );
-    response
+/// Reads `WRITE_RATE_PER_MIN`, the number of write requests one principal may make per
+/// minute. Unset or non-positive values fall back to [`DEFAULT_WRITE_RATE_PER_MIN`].
+pub fn write_rate_per_min_from_env() -> u32 {
+    std::env::var("WRITE_RATE_PER_MIN")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|rate| *rate > 0)
+        .unwrap_or(DEFAULT_WRITE_RATE_PER_MIN)
 }
 
-pub async fn auth_middleware(
-    axum::extract::State(state): axum::extract::State<AuthLayerState>,
-    mut req: AxumRequest,
-    next: Next,
-) -> Response {
-    let path = req.uri().path();
-    if path == "/healthz"
-        || path == "/readyz"
-        || state.allowlist.iter().any(|allowed| allowed == path)
-    {
-        return next.run(req).await;
+/// Reads `CORS_ALLOWED_ORIGINS`, a comma-separated list of exact origins such as
+/// `https://app.lifeready.co.za`. Unset or blank allows no cross-origin callers, so a
+/// deployment that forgets the variable stays closed. `*` is refused because bearer
+/// tokens should only ever be sent from known frontends.
+pub fn cors_allowed_origins_from_env() -> Result<Vec<HeaderValue>, String> {
+    let Ok(value) = std::env::var("CORS_ALLOWED_ORIGINS") else {
+        return Ok(Vec::new());
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(|origin| {
+            if origin == "*" {
+                return Err("CORS_ALLOWED_ORIGINS must list origins explicitly, not *".into());
+            }
+            if !(origin.starts_with("https://") || origin.starts_with("http://"))
+                || origin.ends_with('/')
+            {
+                return Err(format!(
+                    "CORS_ALLOWED_ORIGINS entry {origin:?} must be a scheme://host[:port] origin"
+                ));
+            }
+            HeaderValue::from_str(origin)
+                .map_err(|_| format!("CORS_ALLOWED_ORIGINS entry {origin:?} is not a valid origin"))
+        })
+        .collect()
+}
+
+/// CORS for a service's browser clients: only `origins` may call, with the given
+/// `methods` and request headers on top of `Authorization`, `Content-Type` and
+/// `Idempotency-Key`. The layer answers preflight `OPTIONS` itself, so mount it outside
+/// [`AuthLayer`] and preflights never need a token.
+pub fn cors_layer(
+    origins: Vec<HeaderValue>,
+    methods: &[axum::http::Method],
+    extra_headers: &[header::HeaderName],
+) -> tower_http::cors::CorsLayer {
+    let mut headers = vec![
+        header::AUTHORIZATION,
+        header::CONTENT_TYPE,
+        header::HeaderName::from_static("idempotency-key"),
+    ];
+    headers.extend_from_slice(extra_headers);
+    tower_http::cors::CorsLayer::new()
+        .allow_origin(tower_http::cors::AllowOrigin::list(origins))
+        .allow_methods(methods.to_vec())
+        .allow_headers(headers)
+        .expose_headers([
+            header::HeaderName::from_static(REQUEST_ID_HEADER),
+            header::LOCATION,
+            header::ETAG,
+            header::RETRY_AFTER,
+            header::CONTENT_RANGE,
+        ])
+}
+
+/// `?created_after=` / `?created_before=` bounds shared by list endpoints: RFC 3339
+/// timestamps, `after` inclusive and `before` exclusive, so adjacent windows never
+/// overlap. Either side may be open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CreatedWindow {
+    pub after: Option<chrono::DateTime<Utc>>,
+    pub before: Option<chrono::DateTime<Utc>>,
+}
+
+impl CreatedWindow {
+    /// Parses both bounds, rejecting malformed timestamps and windows that end before
+    /// they start. The error is a client-facing detail for `invalid_request`.
+    pub fn parse(after: Option<&str>, before: Option<&str>) -> Result<Self, String> {
+        let parse = |name: &str, value: Option<&str>| {
+            value
+                .map(|value| {
+                    chrono::DateTime::parse_from_rfc3339(value)
+                        .map(|value| value.with_timezone(&Utc))
+                        .map_err(|_| format!("{name} must be an RFC 3339 timestamp"))
+                })
+                .transpose()
+        };
+        let window = Self {
+            after: parse("created_after", after)?,
+            before: parse("created_before", before)?,
+        };
+        if let (Some(after), Some(before)) = (window.after, window.before)
+            && after > before
+        {
+            return Err("created_after must not be later than created_before".to_string());
+        }
+        Ok(window)
     }
+}
 
-    let request_id = req
-        .extensions()
-        .get::<RequestId>()
-        .copied()
-        .unwrap_or_else(|| RequestId(Uuid::new_v4()));
-    req.extensions_mut().insert(request_id);
-    let token = match bearer_token(req.headers()) {
-        Ok(token) => token,
-        Err(error) => return error.into_response(Some(request_id)),
-    };
+/// Paging metadata returned alongside list `items`. `total` is only computed on request
+/// (`?with_total=true`) because the window count touches every matching row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct PageMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    pub limit: i64,
+    pub returned: usize,
+    /// Pass back as `?cursor=` to fetch the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
 
-    let claims = match state.config.decode_token(token) {
-        Ok(claims) => claims,
-        Err(error) => return error.into_response(Some(request_id)),
-    };
+/// Keyset position for lists ordered by `created_at DESC, id DESC`. Encoded as
+/// `<created_at micros>.<id>`, which survives a query string unescaped; Postgres keeps
+/// microsecond precision, so the position round-trips exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageCursor {
+    pub created_at: chrono::DateTime<Utc>,
+    pub id: Uuid,
+}
 
-    let ctx = RequestContext::from_claims(request_id, &claims);
-    req.extensions_mut().insert(claims);
-    req.extensions_mut().insert(ctx);
-    next.run(req).await
+impl PageCursor {
+    pub fn encode(&self) -> String {
+        format!(
+            "{}.{}",
+            self.created_at.timestamp_micros(),
+            self.id.simple()
+        )
+    }
+
+    /// Parses `?cursor=`; the error is a client-facing detail for `invalid_request`.
+    pub fn parse(value: Option<&str>) -> Result<Option<Self>, String> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        value
+            .split_once('.')
+            .and_then(|(micros, id)| {
+                Some(Self {
+                    created_at: chrono::DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+                    id: Uuid::parse_str(id).ok()?,
+                })
+            })
+            .map(Some)
+            .ok_or_else(|| "invalid cursor".to_string())
+    }
 }
 
-fn bearer_token(headers: &HeaderMap) -> Result<&str, AuthError> {
-    let value = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|value| value.to_str().ok())
-        .ok_or_else(|| AuthError::unauthorized("missing bearer token"))?;
+pub const DEFAULT_MAX_JSON_BODY_BYTES: usize = 1024 * 1024;
+
+/// Reads `MAX_JSON_BODY_BYTES`, the largest request body a router buffers for JSON
+/// handlers. Routes that take uploads set their own larger limit. Unset or non-positive
+/// values fall back to [`DEFAULT_MAX_JSON_BODY_BYTES`].
+pub fn max_json_body_bytes_from_env() -> usize {
+    std::env::var("MAX_JSON_BODY_BYTES")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_MAX_JSON_BODY_BYTES)
+}
 
-    let value = value
-        .strip_prefix("Bearer ")
-        .ok_or_else(|| AuthError::unauthorized("invalid authorization scheme"))?;
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_LONG_REQUEST_TIMEOUT_SECS: u64 = 300;
 
-    if value.trim().is_empty() {
-        return Err(AuthError::unauthorized("empty bearer token"));
+/// Per-request deadlines, after which the handler future is dropped and the client gets
+/// `504 Gateway Timeout`. Dropping the future rolls back any open transaction and runs
+/// drop guards such as a partial-export cleanup, so a timed-out request leaves nothing
+/// behind. `long` covers exports and downloads that stream whole bundles from storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestTimeouts {
+    pub default: std::time::Duration,
+    pub long: std::time::Duration,
+}
+
+impl RequestTimeouts {
+    /// Reads `REQUEST_TIMEOUT_SECS` and `REQUEST_TIMEOUT_LONG_SECS`. Unset, unparsable or
+    /// zero values fall back to the defaults, and `long` is never shorter than `default`.
+    pub fn from_env() -> Self {
+        let secs = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(default)
+        };
+        let default = secs("REQUEST_TIMEOUT_SECS", DEFAULT_REQUEST_TIMEOUT_SECS);
+        let long = secs(
+            "REQUEST_TIMEOUT_LONG_SECS",
+            DEFAULT_LONG_REQUEST_TIMEOUT_SECS,
+        );
+        Self {
+            default: std::time::Duration::from_secs(default),
+            long: std::time::Duration::from_secs(long.max(default)),
+        }
     }
 
-    Ok(value)
+    pub fn default_layer(&self) -> tower_http::timeout::TimeoutLayer {
+        tower_http::timeout::TimeoutLayer::with_status_code(
+            StatusCode::GATEWAY_TIMEOUT,
+            self.default,
+        )
+    }
+
+    pub fn long_layer(&self) -> tower_http::timeout::TimeoutLayer {
+        tower_http::timeout::TimeoutLayer::with_status_code(StatusCode::GATEWAY_TIMEOUT, self.long)
+    }
 }
 
-pub fn access_denied(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
-    AuthError::forbidden(detail).into_response(request_id)
+pub const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 30;
+
+/// Reads `SHUTDOWN_GRACE_SECS`, how long in-flight requests (long exports in particular)
+/// may keep running after a shutdown signal. Unset or unparsable values fall back to
+/// [`DEFAULT_SHUTDOWN_GRACE_SECS`].
+pub fn shutdown_grace_from_env() -> std::time::Duration {
+    std::time::Duration::from_secs(
+        std::env::var("SHUTDOWN_GRACE_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_SHUTDOWN_GRACE_SECS),
+    )
 }
 
-pub fn invalid_request(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
-    AuthError::invalid(detail).into_response(request_id)
+/// Resolves on Ctrl-C or, on Unix, `SIGTERM` (what orchestrators send before killing a pod).
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %error, "failed to listen for ctrl-c");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
 
-pub fn not_found(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
-    problem_response(
-        StatusCode::NOT_FOUND,
-        "https://errors.lifeready.local/request/not-found",
-        "Not found",
-        Some(detail.into()),
-        request_id.map(|id| id.0),
+/// Binds `addr` and serves `router` until [`shutdown_signal`] fires, then drains in-flight
+/// requests for up to [`shutdown_grace_from_env`].
+pub async fn serve_with_shutdown(router: axum::Router, addr: SocketAddr) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    serve_listener_with_shutdown(
+        listener,
+        router,
+        shutdown_signal(),
+        shutdown_grace_from_env(),
     )
+    .await
 }
 
-pub fn conflict(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
-    problem_response(
-        StatusCode::CONFLICT,
-        "https://errors.lifeready.local/request/conflict",
-        "Conflict",
-        Some(detail.into()),
-        request_id.map(|id| id.0),
+/// Serves `router` on `listener`. Once `shutdown` resolves no new connections are
+/// accepted and in-flight requests get `grace` to finish. Past that the server returns
+/// anyway; requests still running are dropped when the runtime stops, which runs their
+/// cleanup guards (a half-written export removes itself) rather than leaving partial files.
+pub async fn serve_listener_with_shutdown<F>(
+    listener: tokio::net::TcpListener,
+    router: axum::Router,
+    shutdown: F,
+    grace: std::time::Duration,
+) -> std::io::Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (signalled_tx, signalled_rx) = tokio::sync::oneshot::channel::<()>();
+    let server = axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(async move {
+        shutdown.await;
+        tracing::info!("shutdown signal received; draining in-flight requests");
+        let _ = signalled_tx.send(());
+    });
+    let deadline = async move {
+        if signalled_rx.await.is_err() {
+            std::future::pending::<()>().await;
+        }
+        tokio::time::sleep(grace).await;
+    };
+
+    tokio::select! {
+        result = server => result,
+        _ = deadline => {
+            tracing::warn!(grace_secs = grace.as_secs(), "shutdown grace elapsed; dropping in-flight requests");
+            Ok(())
+        }
+    }
 }
 
-pub fn ok_response<T: Serialize>(payload: T) -> Response {
-    Json(json!(payload)).into_response()
+/// Decides whether `key` may make another request now. Implementations must be shareable
+/// across services so a Redis-backed limiter can replace the in-memory one.
+pub trait RateLimiter: Send + Sync {
+    /// `Err` carries how long the caller should wait before retrying.
+    fn check(&self, key: &str) -> Result<(), std::time::Duration>;
 }
 
-#[cfg(test)]
-mod tests {
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: std::time::Instant,
+}
+
+/// Per-process token bucket per key: `per_minute` requests may burst at once, then the
+/// bucket refills continuously at `per_minute` tokens a minute. A bucket left idle for a
+/// minute is full again, the same as a missing one, so such buckets are evicted at most
+/// once a minute to keep one-off callers from growing the map forever.
+pub struct InMemoryRateLimiter {
+    per_minute: u32,
+    buckets: dashmap::DashMap<String, TokenBucket>,
+    swept_at: std::sync::Mutex<std::time::Instant>,
+}
+
+const RATE_LIMIT_REFILL_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+impl InMemoryRateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute: per_minute.max(1),
+            buckets: dashmap::DashMap::new(),
+            swept_at: std::sync::Mutex::new(std::time::Instant::now()),
+        }
+    }
+
+    fn evict_idle(&self, now: std::time::Instant) {
+        {
+            let mut swept_at = self
+                .swept_at
+                .lock()
+                .unwrap_or_else(|error| error.into_inner());
+            if now.saturating_duration_since(*swept_at) < RATE_LIMIT_REFILL_WINDOW {
+                return;
+            }
+            *swept_at = now;
+        }
+        self.buckets.retain(|_, bucket| {
+            now.saturating_duration_since(bucket.refilled_at) < RATE_LIMIT_REFILL_WINDOW
+        });
+    }
+}
+
+impl RateLimiter for InMemoryRateLimiter {
+    fn check(&self, key: &str) -> Result<(), std::time::Duration> {
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+        let now = std::time::Instant::now();
+        self.evict_idle(now);
+        let mut bucket = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: capacity,
+                refilled_at: now,
+            });
+
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(std::time::Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / per_second,
+            ))
+        }
+    }
+}
+
+/// Throttles POST, PUT and PATCH requests per authenticated principal. It reads the
+/// `RequestContext` the auth layer inserts, so it must sit inside `AuthLayer`; reads and
+/// unauthenticated routes pass straight through.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<dyn RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: Arc<dyn RateLimiter>) -> Self {
+        Self { limiter }
+    }
+
+    /// In-memory limiter sized by `WRITE_RATE_PER_MIN`.
+    pub fn from_env() -> Self {
+        Self::new(Arc::new(InMemoryRateLimiter::new(
+            write_rate_per_min_from_env(),
+        )))
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<dyn RateLimiter>,
+}
+
+impl<S, B> Service<Request<B>> for RateLimitService<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let is_write = matches!(
+            *req.method(),
+            axum::http::Method::POST | axum::http::Method::PUT | axum::http::Method::PATCH
+        );
+        let verdict = match req.extensions().get::<RequestContext>() {
+            Some(ctx) if is_write => self
+                .limiter
+                .check(&ctx.principal_id)
+                .map_err(|retry_after| (ctx.request_id, retry_after)),
+            _ => Ok(()),
+        };
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            match verdict {
+                Ok(()) => inner.call(req).await,
+                Err((request_id, retry_after)) => {
+                    Ok(too_many_requests(Some(request_id), retry_after))
+                }
+            }
+        })
+    }
+}
+
+/// Assigns the request id and wraps the request in an `http.request` span carrying the
+/// id, method, path, and any case/document id in the path. The auth layer fills in the
+/// principal; status and latency are recorded and logged once the response is ready.
+pub async fn request_id_middleware(mut req: AxumRequest, next: Next) -> Response {
+    let request_id =
+        RequestId::from_headers(req.headers()).unwrap_or_else(|| RequestId(Uuid::new_v4()));
+    req.extensions_mut().insert(request_id);
+
+    let path = req.uri().path().to_string();
+    let span = tracing::info_span!(
+        "http.request",
+        request_id = %request_id.0,
+        method = %req.method(),
+        path = %path,
+        case_id = tracing::field::Empty,
+        document_id = tracing::field::Empty,
+        principal_id = tracing::field::Empty,
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+    );
+    if let Some(case_id) = path_resource_id(&path, "cases") {
+        span.record("case_id", tracing::field::display(case_id));
+    }
+    if let Some(document_id) = path_resource_id(&path, "documents") {
+        span.record("document_id", tracing::field::display(document_id));
+    }
+
+    let started = std::time::Instant::now();
+    let mut response = next.run(req).instrument(span.clone()).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    span.record("status", response.status().as_u16());
+    span.record("latency_ms", latency_ms);
+    span.in_scope(|| {
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms,
+            "request completed"
+        )
+    });
+
+    response.headers_mut().insert(
+        REQUEST_ID_HEADER,
+        HeaderValue::from_str(&request_id.0.to_string())
+            .unwrap_or_else(|_| HeaderValue::from_static("")),
+    );
+    response
+}
+
+pub async fn auth_middleware(
+    axum::extract::State(state): axum::extract::State<AuthLayerState>,
+    mut req: AxumRequest,
+    next: Next,
+) -> Response {
+    let path = req.uri().path();
+    if path == "/healthz"
+        || path == "/readyz"
+        || path == OPENAPI_PATH
+        || path == METRICS_PATH
+        || state.allowlist.iter().any(|allowed| allowed == path)
+    {
+        return next.run(req).await;
+    }
+
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .copied()
+        .unwrap_or_else(|| RequestId(Uuid::new_v4()));
+    req.extensions_mut().insert(request_id);
+    let token = match bearer_token(req.headers()) {
+        Ok(token) => token,
+        Err(error) => return error.into_response(Some(request_id)),
+    };
+
+    let claims = match state.config.decode_token(token) {
+        Ok(claims) => claims,
+        Err(error) => return error.into_response(Some(request_id)),
+    };
+
+    if !state.revoked.ensure_loaded().await {
+        return revocations_unavailable(request_id);
+    }
+    if state.revoked.is_revoked(&claims.jti) {
+        return AuthError::unauthorized("token has been revoked").into_response(Some(request_id));
+    }
+
+    let ctx = RequestContext::from_claims(request_id, &claims);
+    tracing::Span::current().record("principal_id", ctx.principal_id.as_str());
+    req.extensions_mut().insert(claims);
+    req.extensions_mut().insert(ctx);
+    next.run(req).await
+}
+
+/// Returns the UUID segment following `collection` in `path`, e.g. the case id in
+/// `/v1/cases/{case_id}/export`.
+fn path_resource_id(path: &str, collection: &str) -> Option<Uuid> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == collection)?;
+    segments
+        .next()
+        .and_then(|segment| Uuid::parse_str(segment).ok())
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<&str, AuthError> {
+    let value = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AuthError::unauthorized("missing bearer token"))?;
+
+    let value = value
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| AuthError::unauthorized("invalid authorization scheme"))?;
+
+    if value.trim().is_empty() {
+        return Err(AuthError::unauthorized("empty bearer token"));
+    }
+
+    Ok(value)
+}
+
+pub fn access_denied(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    AuthError::forbidden(detail).into_response(request_id)
+}
+
+pub fn invalid_request(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    AuthError::invalid(detail).into_response(request_id)
+}
+
+pub fn not_found(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    problem_response(
+        StatusCode::NOT_FOUND,
+        "https://errors.lifeready.local/request/not-found",
+        "Not found",
+        "not_found",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 409 whose `code` names the specific conflict (e.g. `evidence_incomplete`).
+pub fn conflict(request_id: Option<RequestId>, code: &str, detail: impl Into<String>) -> Response {
+    problem_response(
+        StatusCode::CONFLICT,
+        "https://errors.lifeready.local/request/conflict",
+        "Conflict",
+        code,
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+pub fn gone(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    problem_response(
+        StatusCode::GONE,
+        "https://errors.lifeready.local/request/gone",
+        "Gone",
+        "gone",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 412 for a conditional request (`If-Match`) whose precondition does not hold.
+pub fn precondition_failed(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    problem_response(
+        StatusCode::PRECONDITION_FAILED,
+        "https://errors.lifeready.local/request/precondition-failed",
+        "Precondition failed",
+        "precondition_failed",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 415 for content the service cannot accept or process in the given media type.
+pub fn unsupported_media_type(
+    request_id: Option<RequestId>,
+    detail: impl Into<String>,
+) -> Response {
+    problem_response(
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "https://errors.lifeready.local/request/unsupported-media-type",
+        "Unsupported media type",
+        "unsupported_media_type",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 429 with a whole-second `Retry-After`, rounded up so clients never retry early.
+pub fn too_many_requests(
+    request_id: Option<RequestId>,
+    retry_after: std::time::Duration,
+) -> Response {
+    let retry_after_secs = whole_retry_after_secs(retry_after);
+    let mut response = problem_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        "https://errors.lifeready.local/request/rate-limited",
+        "Too many requests",
+        "rate_limited",
+        Some(format!(
+            "write rate limit exceeded; retry in {retry_after_secs}s"
+        )),
+        request_id.map(|id| id.0),
+    );
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    response
+}
+
+/// 501 for an operation the configured backend cannot perform at all, as opposed to a
+/// failure worth retrying.
+pub fn not_implemented(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    problem_response(
+        StatusCode::NOT_IMPLEMENTED,
+        "https://errors.lifeready.local/request/not-implemented",
+        "Not implemented",
+        "not_implemented",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 503 with a whole-second `Retry-After`, for work the service is temporarily too busy
+/// to take on.
+pub fn service_busy(
+    request_id: Option<RequestId>,
+    detail: impl Into<String>,
+    retry_after: std::time::Duration,
+) -> Response {
+    let mut response = problem_response(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "https://errors.lifeready.local/request/busy",
+        "Service busy",
+        "service_busy",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    );
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(whole_retry_after_secs(retry_after)),
+    );
+    response
+}
+
+/// Rounded up and at least one, so clients never retry early.
+fn whole_retry_after_secs(retry_after: std::time::Duration) -> u64 {
+    (retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0)).max(1)
+}
+
+pub fn payload_too_large(request_id: Option<RequestId>) -> Response {
+    problem_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "https://errors.lifeready.local/request/too-large",
+        "Payload too large",
+        "payload_too_large",
+        Some("request body exceeds the size limit".into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 413 for a response the service refuses to build because it would exceed a size cap,
+/// as opposed to [`payload_too_large`] for oversized request bodies.
+pub fn content_too_large(request_id: Option<RequestId>, detail: impl Into<String>) -> Response {
+    problem_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "https://errors.lifeready.local/request/too-large",
+        "Payload too large",
+        "payload_too_large",
+        Some(detail.into()),
+        request_id.map(|id| id.0),
+    )
+}
+
+/// 416 for a `Range` that lies outside a `complete_length`-byte representation; the
+/// `Content-Range: bytes */len` header tells the client what it can ask for instead.
+pub fn range_not_satisfiable(request_id: Option<RequestId>, complete_length: u64) -> Response {
+    let mut response = problem_response(
+        StatusCode::RANGE_NOT_SATISFIABLE,
+        "https://errors.lifeready.local/request/range-not-satisfiable",
+        "Range not satisfiable",
+        "range_not_satisfiable",
+        Some(format!(
+            "requested range is outside the {complete_length}-byte content"
+        )),
+        request_id.map(|id| id.0),
+    );
+    if let Ok(value) = HeaderValue::from_str(&format!("bytes */{complete_length}")) {
+        response.headers_mut().insert(header::CONTENT_RANGE, value);
+    }
+    response
+}
+
+pub fn ok_response<T: Serialize>(payload: T) -> Response {
+    Json(json!(payload)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
     use axum::{
         Router,
@@ -843,6 +2067,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn path_resource_id_extracts_uuid_after_collection() {
+        let case_id = Uuid::new_v4();
+        assert_eq!(
+            path_resource_id(&format!("/v1/cases/{case_id}/export"), "cases"),
+            Some(case_id)
+        );
+        assert_eq!(path_resource_id("/v1/cases/mhca39", "cases"), None);
+        assert_eq!(path_resource_id("/v1/cases", "cases"), None);
+        assert_eq!(
+            path_resource_id(&format!("/v1/cases/{case_id}"), "documents"),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn request_id_middleware_sets_header() {
         let app = Router::new()
@@ -938,6 +2177,90 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn auth_middleware_rejects_tokens_for_another_issuer_or_audience() {
+        let config = AuthConfig::new("test-secret")
+            .with_issuer("lifeready-staging")
+            .with_audience("case-service");
+        let state = AuthLayerState::new(config.clone(), Vec::<String>::new());
+        let app = Router::new()
+            .route(
+                "/protected",
+                get(|_ctx: RequestContext| async { StatusCode::OK }),
+            )
+            .with_state(state.clone())
+            .layer(axum::middleware::from_fn_with_state(state, auth_middleware));
+        let claims = Claims::new(
+            "principal",
+            Role::Principal,
+            vec![SensitivityTier::Green],
+            AccessLevel::ReadOnlyAll,
+            None,
+            60,
+        );
+
+        let wrong_audience = AuthConfig::new("test-secret")
+            .with_issuer("lifeready-staging")
+            .with_audience("vault-service");
+        let wrong_issuer = AuthConfig::new("test-secret")
+            .with_issuer("lifeready-production")
+            .with_audience("case-service");
+        for (issuer, expected) in [
+            (&wrong_audience, StatusCode::UNAUTHORIZED),
+            (&wrong_issuer, StatusCode::UNAUTHORIZED),
+            (&config, StatusCode::OK),
+        ] {
+            let token = issuer.issue_token(&claims).expect("token");
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri("/protected")
+                        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .expect("response");
+            assert_eq!(response.status(), expected);
+        }
+    }
+
+    #[test]
+    fn from_env_checked_requires_configured_issuer_and_audience() {
+        with_env(
+            &[
+                ("LIFEREADY_ENV", Some("dev")),
+                ("JWT_SECRET", Some("test-secret-32-chars-minimum!!")),
+                ("JWT_ISSUER", Some("lifeready-staging")),
+                ("JWT_AUDIENCE", Some("case-service")),
+            ],
+            || {
+                let config = AuthConfig::from_env_checked().expect("config");
+                let claims = Claims::new(
+                    "user",
+                    Role::Principal,
+                    vec![SensitivityTier::Green],
+                    AccessLevel::ReadOnlyAll,
+                    None,
+                    60,
+                );
+                let token = config.issue_token(&claims).expect("token");
+                let decoded = config.decode_token(&token).expect("decoded");
+                assert_eq!(decoded.iss.as_deref(), Some("lifeready-staging"));
+                assert_eq!(decoded.aud.as_deref(), Some("case-service"));
+
+                let unbound = AuthConfig::new("test-secret-32-chars-minimum!!")
+                    .issue_token(&claims)
+                    .expect("token");
+                assert!(matches!(
+                    config.decode_token(&unbound),
+                    Err(AuthError::Unauthorized { .. })
+                ));
+            },
+        );
+    }
+
     #[tokio::test]
     async fn auth_middleware_allows_readyz_without_token() {
         let config = AuthConfig::new("test-secret");
@@ -984,6 +2307,50 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[test]
+    fn signatures_verify_only_for_the_signed_message_and_key() {
+        let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+        let signature = config.sign("doc:version:1700000000");
+
+        assert!(config.verify_signature("doc:version:1700000000", &signature));
+        assert!(!config.verify_signature("doc:version:1700000001", &signature));
+        assert!(!config.verify_signature("doc:version:1700000000", "not-hex"));
+
+        let other = AuthConfig::new("another-secret-32-chars-minimum!");
+        assert!(!other.verify_signature("doc:version:1700000000", &signature));
+    }
+
+    #[test]
+    fn token_pair_refresh_token_round_trips_only_under_the_issuing_key() {
+        let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+        let claims = Claims::new(
+            "principal-1",
+            Role::Principal,
+            vec![SensitivityTier::Green],
+            AccessLevel::ReadOnlyAll,
+            None,
+            300,
+        );
+        let pair = config.issue_token_pair(&claims).expect("pair");
+
+        assert_eq!(
+            config.decode_token(&pair.access_token).unwrap().sub,
+            "principal-1"
+        );
+        assert_eq!(
+            config.refresh_token_id(&pair.refresh_token),
+            Some(pair.refresh_token_id)
+        );
+        assert!(config.decode_token(&pair.refresh_token).is_err());
+
+        let other = AuthConfig::new("another-secret-32-chars-minimum!");
+        assert_eq!(other.refresh_token_id(&pair.refresh_token), None);
+
+        let forged = format!("{}.{}", Uuid::new_v4(), "00".repeat(32));
+        assert_eq!(config.refresh_token_id(&forged), None);
+        assert_eq!(config.refresh_token_id("not-a-token"), None);
+    }
+
     #[test]
     fn response_helpers_set_expected_statuses() {
         let request_id = RequestId(Uuid::new_v4());
@@ -997,13 +2364,44 @@ mod tests {
         let missing = not_found(Some(request_id), "missing");
         assert_eq!(missing.status(), StatusCode::NOT_FOUND);
 
-        let conflict_response = conflict(Some(request_id), "conflict");
+        let conflict_response = conflict(Some(request_id), "duplicate_record", "conflict");
         assert_eq!(conflict_response.status(), StatusCode::CONFLICT);
 
+        let gone_response = gone(Some(request_id), "gone");
+        assert_eq!(gone_response.status(), StatusCode::GONE);
+
         let ok = ok_response(serde_json::json!({"ok": true}));
         assert_eq!(ok.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn problem_bodies_carry_code_and_instance() {
+        let request_id = RequestId(Uuid::new_v4());
+        let response = conflict(
+            Some(request_id),
+            "evidence_incomplete",
+            "evidence slots incomplete",
+        );
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["code"], "evidence_incomplete");
+        assert_eq!(problem["detail"], "evidence slots incomplete");
+        assert_eq!(problem["instance"], format!("urn:uuid:{}", request_id.0));
+
+        let body = axum::body::to_bytes(not_found(None, "missing").into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["code"], "not_found");
+        assert!(problem["instance"].is_null());
+    }
+
     #[test]
     fn from_env_checked_production_rejects_missing_secret() {
         with_env(
@@ -1072,4 +2470,502 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn in_memory_rate_limiter_exhausts_per_key() {
+        let limiter = InMemoryRateLimiter::new(2);
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("a").is_ok());
+        let retry_after = limiter.check("a").expect_err("bucket exhausted");
+        assert!(retry_after > std::time::Duration::ZERO);
+        assert!(retry_after <= std::time::Duration::from_secs(30));
+        assert!(limiter.check("b").is_ok());
+    }
+
+    #[test]
+    fn in_memory_rate_limiter_evicts_idle_buckets() {
+        let limiter = InMemoryRateLimiter::new(2);
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("b").is_ok());
+        let now = std::time::Instant::now();
+        limiter.evict_idle(now + std::time::Duration::from_secs(30));
+        assert_eq!(limiter.buckets.len(), 2);
+        limiter.evict_idle(now + RATE_LIMIT_REFILL_WINDOW + std::time::Duration::from_secs(1));
+        assert!(limiter.buckets.is_empty());
+    }
+
+    #[test]
+    fn write_rate_per_min_defaults_and_overrides() {
+        with_env(&[("WRITE_RATE_PER_MIN", None)], || {
+            assert_eq!(write_rate_per_min_from_env(), DEFAULT_WRITE_RATE_PER_MIN);
+        });
+        with_env(&[("WRITE_RATE_PER_MIN", Some("5"))], || {
+            assert_eq!(write_rate_per_min_from_env(), 5);
+        });
+        with_env(&[("WRITE_RATE_PER_MIN", Some("0"))], || {
+            assert_eq!(write_rate_per_min_from_env(), DEFAULT_WRITE_RATE_PER_MIN);
+        });
+    }
+
+    #[tokio::test]
+    async fn rate_limit_layer_throttles_writes_only() {
+        let config = Arc::new(AuthConfig::new("test-secret"));
+        let token = config
+            .issue_token(&Claims::new(
+                "user",
+                Role::Principal,
+                vec![SensitivityTier::Green],
+                AccessLevel::LimitedWrite,
+                None,
+                60,
+            ))
+            .expect("token");
+        let app = Router::new()
+            .route(
+                "/items",
+                get(|| async { StatusCode::OK }).post(|| async { StatusCode::CREATED }),
+            )
+            .layer(RateLimitLayer::new(Arc::new(InMemoryRateLimiter::new(1))))
+            .layer(AuthLayer::new(config));
+        let send = |method: &str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri("/items")
+                    .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        assert_eq!(send("POST").await.unwrap().status(), StatusCode::CREATED);
+        let response = send("POST").await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after >= 1);
+        assert_eq!(send("GET").await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn auth_layer_rejects_revoked_tokens_only() {
+        let config = Arc::new(AuthConfig::new("test-secret"));
+        let claims = |sub: &str| {
+            Claims::new(
+                sub,
+                Role::Principal,
+                vec![SensitivityTier::Green],
+                AccessLevel::ReadOnlyAll,
+                None,
+                60,
+            )
+        };
+        let leaked = claims("user-a");
+        let leaked_token = config.issue_token(&leaked).expect("token");
+        let other_token = config.issue_token(&claims("user-b")).expect("token");
+
+        let revoked = RevokedTokens::default();
+        let app = Router::new()
+            .route("/items", get(|| async { StatusCode::OK }))
+            .layer(AuthLayer::new(config).with_revocations(revoked.clone()));
+        let send = |token: &str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .uri("/items")
+                    .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        assert_eq!(send(&leaked_token).await.unwrap().status(), StatusCode::OK);
+        revoked.revoke(leaked.jti.clone(), Utc::now() + Duration::seconds(60));
+        assert_eq!(
+            send(&leaked_token).await.unwrap().status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(send(&other_token).await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn auth_layer_refuses_tokens_while_revocations_cannot_load() {
+        let config = Arc::new(AuthConfig::new("test-secret"));
+        let token = config
+            .issue_token(&Claims::new(
+                "user-a",
+                Role::Principal,
+                vec![SensitivityTier::Green],
+                AccessLevel::ReadOnlyAll,
+                None,
+                60,
+            ))
+            .expect("token");
+        let unreachable = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(200))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+        let revoked = RevokedTokens::backed_by(unreachable, std::time::Duration::from_secs(30));
+        let app = Router::new()
+            .route("/items", get(|| async { StatusCode::OK }))
+            .layer(AuthLayer::new(config).with_revocations(revoked));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/items")
+                    .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn revoked_tokens_forget_entries_once_the_token_expires() {
+        let revoked = RevokedTokens::default();
+        revoked.revoke("live", Utc::now() + Duration::seconds(60));
+        revoked.revoke("stale", Utc::now() - Duration::seconds(1));
+
+        assert!(revoked.is_revoked("live"));
+        assert!(!revoked.is_revoked("stale"));
+        revoked.purge_expired();
+        assert_eq!(revoked.entries.len(), 1);
+    }
+
+    #[test]
+    fn cors_origins_parse_and_reject_wildcards() {
+        with_env(&[("CORS_ALLOWED_ORIGINS", None)], || {
+            assert!(cors_allowed_origins_from_env().unwrap().is_empty());
+        });
+        with_env(
+            &[(
+                "CORS_ALLOWED_ORIGINS",
+                Some("https://app.lifeready.example, http://localhost:5173,"),
+            )],
+            || {
+                assert_eq!(
+                    cors_allowed_origins_from_env().unwrap(),
+                    vec![
+                        HeaderValue::from_static("https://app.lifeready.example"),
+                        HeaderValue::from_static("http://localhost:5173"),
+                    ]
+                );
+            },
+        );
+        for bad in [
+            "*",
+            "app.lifeready.example",
+            "https://app.lifeready.example/",
+        ] {
+            with_env(&[("CORS_ALLOWED_ORIGINS", Some(bad))], || {
+                assert!(cors_allowed_origins_from_env().is_err(), "{bad} accepted");
+            });
+        }
+    }
+
+    async fn send_raw_get(addr: SocketAddr) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: test\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).await;
+        response
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_drains_in_flight_requests() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                "done"
+            }),
+        );
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve_listener_with_shutdown(
+            listener,
+            app,
+            async move {
+                let _ = shutdown_rx.await;
+            },
+            std::time::Duration::from_secs(5),
+        ));
+
+        let request = tokio::spawn(send_raw_get(addr));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let _ = shutdown_tx.send(());
+
+        let response = request.await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.ends_with("done"));
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_gives_up_after_grace() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                "done"
+            }),
+        );
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve_listener_with_shutdown(
+            listener,
+            app,
+            async move {
+                let _ = shutdown_rx.await;
+            },
+            std::time::Duration::from_millis(100),
+        ));
+
+        let _request = tokio::spawn(send_raw_get(addr));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let _ = shutdown_tx.send(());
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), server)
+            .await
+            .expect("server kept waiting past the grace period")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn shutdown_grace_reads_env() {
+        with_env(&[("SHUTDOWN_GRACE_SECS", None)], || {
+            assert_eq!(
+                shutdown_grace_from_env(),
+                std::time::Duration::from_secs(DEFAULT_SHUTDOWN_GRACE_SECS)
+            );
+        });
+        with_env(&[("SHUTDOWN_GRACE_SECS", Some("5"))], || {
+            assert_eq!(shutdown_grace_from_env(), std::time::Duration::from_secs(5));
+        });
+    }
+
+    #[test]
+    fn request_timeouts_read_env() {
+        let secs = std::time::Duration::from_secs;
+        with_env(
+            &[
+                ("REQUEST_TIMEOUT_SECS", None),
+                ("REQUEST_TIMEOUT_LONG_SECS", None),
+            ],
+            || {
+                assert_eq!(
+                    RequestTimeouts::from_env(),
+                    RequestTimeouts {
+                        default: secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+                        long: secs(DEFAULT_LONG_REQUEST_TIMEOUT_SECS),
+                    }
+                );
+            },
+        );
+        with_env(
+            &[
+                ("REQUEST_TIMEOUT_SECS", Some("10")),
+                ("REQUEST_TIMEOUT_LONG_SECS", Some("0")),
+            ],
+            || {
+                assert_eq!(
+                    RequestTimeouts::from_env(),
+                    RequestTimeouts {
+                        default: secs(10),
+                        long: secs(DEFAULT_LONG_REQUEST_TIMEOUT_SECS),
+                    }
+                );
+            },
+        );
+        with_env(
+            &[
+                ("REQUEST_TIMEOUT_SECS", Some("600")),
+                ("REQUEST_TIMEOUT_LONG_SECS", Some("60")),
+            ],
+            || {
+                let timeouts = RequestTimeouts::from_env();
+                assert_eq!(timeouts.long, secs(600));
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn request_timeout_layer_answers_gateway_timeout() {
+        let timeouts = RequestTimeouts {
+            default: std::time::Duration::from_millis(20),
+            long: std::time::Duration::from_secs(5),
+        };
+        let slow = || async {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            "done"
+        };
+        let app = axum::Router::new()
+            .route("/slow", axum::routing::get(slow))
+            .layer(timeouts.default_layer())
+            .route(
+                "/slow-allowed",
+                axum::routing::get(slow).layer(timeouts.long_layer()),
+            );
+
+        let status = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+        assert_eq!(status("/slow").await, StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(status("/slow-allowed").await, StatusCode::OK);
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct JsonBodyFixture {
+        #[allow(dead_code)]
+        title: String,
+        #[allow(dead_code)]
+        contacts: Vec<JsonBodyContact>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct JsonBodyContact {
+        #[allow(dead_code)]
+        phone: String,
+    }
+
+    #[test]
+    fn json_body_errors_name_the_field() {
+        let missing = parse_json_body::<JsonBodyFixture>(br#"{"contacts": []}"#).unwrap_err();
+        assert_eq!(missing, "invalid request body: missing field `title`");
+
+        let nested =
+            parse_json_body::<JsonBodyFixture>(br#"{"title": "t", "contacts": [{"phone": 7}]}"#)
+                .unwrap_err();
+        assert!(
+            nested.starts_with("invalid request body: contacts[0].phone: invalid type"),
+            "{nested}"
+        );
+
+        let syntax = parse_json_body::<JsonBodyFixture>(b"{").unwrap_err();
+        assert!(syntax.starts_with("malformed JSON body:"), "{syntax}");
+    }
+
+    #[tokio::test]
+    async fn json_body_rejections_are_problem_json() {
+        let app = Router::new()
+            .route(
+                "/items",
+                axum::routing::post(|JsonBody(_): JsonBody<JsonBodyFixture>| async {
+                    StatusCode::CREATED
+                }),
+            )
+            .layer(axum::middleware::from_fn(request_id_middleware));
+        let post = |content_type: &'static str, body: &'static str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/items")
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+
+        let response = post("application/json", r#"{"contacts": []}"#)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let request_id = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["code"], "invalid_request");
+        assert_eq!(
+            problem["detail"],
+            "invalid request body: missing field `title`"
+        );
+        assert!(problem["instance"].as_str().unwrap().contains(&request_id));
+
+        let response = post("text/plain", "{}").await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = post(
+            "application/json; charset=utf-8",
+            r#"{"title": "t", "contacts": []}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[test]
+    fn page_cursor_round_trips_and_rejects_garbage() {
+        let cursor = PageCursor {
+            created_at: Utc.timestamp_micros(1_735_689_600_123_456).unwrap(),
+            id: Uuid::new_v4(),
+        };
+        assert_eq!(PageCursor::parse(Some(&cursor.encode())), Ok(Some(cursor)));
+        assert_eq!(PageCursor::parse(None), Ok(None));
+        for bad in ["", "123", "abc.def", "1.not-a-uuid"] {
+            assert_eq!(
+                PageCursor::parse(Some(bad)),
+                Err("invalid cursor".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn created_window_parses_bounds_and_rejects_inverted_ranges() {
+        let window = CreatedWindow::parse(
+            Some("2025-01-01T00:00:00Z"),
+            Some("2025-02-01T02:00:00+02:00"),
+        )
+        .unwrap();
+        assert_eq!(
+            window.after,
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            window.before,
+            Some(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap())
+        );
+
+        assert_eq!(
+            CreatedWindow::parse(None, None),
+            Ok(CreatedWindow::default())
+        );
+        assert!(
+            CreatedWindow::parse(Some("2025-01-01T00:00:00Z"), Some("2025-01-01T00:00:00Z"))
+                .is_ok()
+        );
+        assert_eq!(
+            CreatedWindow::parse(Some("2025-02-01T00:00:00Z"), Some("2025-01-01T00:00:00Z")),
+            Err("created_after must not be later than created_before".to_string())
+        );
+        assert_eq!(
+            CreatedWindow::parse(Some("yesterday"), None),
+            Err("created_after must be an RFC 3339 timestamp".to_string())
+        );
+    }
 }
@@ -0,0 +1,103 @@
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Connection pool sizing from `DB_MAX_CONNECTIONS` (default 10), `DB_ACQUIRE_TIMEOUT_SECS`
+/// (default 5) and `DB_IDLE_TIMEOUT_SECS` (default 600). Unparsable or zero values fall
+/// back to the default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DbPoolSettings {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Duration,
+}
+
+impl Default for DbPoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            acquire_timeout: Duration::from_secs(5),
+            idle_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl DbPoolSettings {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        fn positive<T: std::str::FromStr + PartialOrd + Default>(
+            value: Option<String>,
+            default: T,
+        ) -> T {
+            value
+                .and_then(|value| value.trim().parse::<T>().ok())
+                .filter(|value| *value > T::default())
+                .unwrap_or(default)
+        }
+        let defaults = Self::default();
+        Self {
+            max_connections: positive(lookup("DB_MAX_CONNECTIONS"), defaults.max_connections),
+            acquire_timeout: Duration::from_secs(positive(
+                lookup("DB_ACQUIRE_TIMEOUT_SECS"),
+                defaults.acquire_timeout.as_secs(),
+            )),
+            idle_timeout: Duration::from_secs(positive(
+                lookup("DB_IDLE_TIMEOUT_SECS"),
+                defaults.idle_timeout.as_secs(),
+            )),
+        }
+    }
+}
+
+/// Lazily connecting pool sized by [`DbPoolSettings`]; the effective settings are logged
+/// so production sizing is visible.
+pub fn configured_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+    let settings = DbPoolSettings::from_env();
+    tracing::info!(
+        max_connections = settings.max_connections,
+        acquire_timeout_secs = settings.acquire_timeout.as_secs(),
+        idle_timeout_secs = settings.idle_timeout.as_secs(),
+        "database pool configured"
+    );
+    sqlx::postgres::PgPoolOptions::new()
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
+        .connect_lazy(database_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_from(vars: &[(&str, &str)]) -> DbPoolSettings {
+        DbPoolSettings::from_lookup(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn db_pool_settings_default_when_unset() {
+        assert_eq!(settings_from(&[]), DbPoolSettings::default());
+    }
+
+    #[test]
+    fn db_pool_settings_ignore_zero_and_unparsable_values() {
+        assert_eq!(
+            settings_from(&[
+                ("DB_MAX_CONNECTIONS", "32"),
+                ("DB_ACQUIRE_TIMEOUT_SECS", "0"),
+                ("DB_IDLE_TIMEOUT_SECS", "abc"),
+            ]),
+            DbPoolSettings {
+                max_connections: 32,
+                acquire_timeout: Duration::from_secs(5),
+                idle_timeout: Duration::from_secs(600),
+            }
+        );
+    }
+}
@@ -3,9 +3,19 @@ use lifeready_auth::{AuthError, RequestContext, RequestId};
 
 pub use lifeready_auth::{Role, SensitivityTier};
 
+/// Which of the caller's allowed tiers satisfy a check. Every variant passes when at
+/// least one allowed tier qualifies.
 #[derive(Debug, Clone)]
 pub enum TierRequirement {
+    /// A tier at or above the given one.
     Min(SensitivityTier),
+    /// A tier at or below the given one.
+    Max(SensitivityTier),
+    /// A tier between `min` and `max`, inclusive.
+    Range {
+        min: SensitivityTier,
+        max: SensitivityTier,
+    },
     Allowlist(Vec<SensitivityTier>),
 }
 
@@ -15,6 +25,28 @@ pub enum PolicyError {
 }
 
 impl PolicyError {
+    /// A denial from one of the `require_*` checks: logged at debug with what was required
+    /// and what the token carried, and counted when the `metrics` feature is on.
+    fn denied(
+        ctx: &RequestContext,
+        check: &'static str,
+        reason: &'static str,
+        required: &dyn std::fmt::Debug,
+        actual: &dyn std::fmt::Debug,
+    ) -> Self {
+        tracing::debug!(
+            principal_id = %ctx.principal_id,
+            check,
+            reason,
+            required = ?required,
+            actual = ?actual,
+            "policy check denied"
+        );
+        #[cfg(feature = "metrics")]
+        metrics::record_denial(check, reason);
+        Self::forbidden(reason.replace('_', " "))
+    }
+
     pub fn forbidden(detail: impl Into<String>) -> Self {
         Self::Forbidden {
             detail: detail.into(),
@@ -38,31 +70,76 @@ pub fn require_role(ctx: &RequestContext, allowed_roles: &[Role]) -> Result<(),
     if ctx.roles.iter().any(|role| allowed_roles.contains(role)) {
         Ok(())
     } else {
-        Err(PolicyError::forbidden("role not permitted"))
+        Err(PolicyError::denied(
+            ctx,
+            "role",
+            "role_not_permitted",
+            &allowed_roles,
+            &ctx.roles,
+        ))
     }
 }
 
+pub fn has_role(ctx: &RequestContext, role: Role) -> bool {
+    ctx.roles.contains(&role)
+}
+
 pub fn require_tier(ctx: &RequestContext, requirement: TierRequirement) -> Result<(), PolicyError> {
     match requirement {
         TierRequirement::Min(min_tier) => {
-            let required_rank = tier_rank(min_tier);
-            let max_rank = ctx
+            if ctx.allowed_tiers.iter().any(|tier| *tier >= min_tier) {
+                Ok(())
+            } else {
+                Err(PolicyError::denied(
+                    ctx,
+                    "tier",
+                    "insufficient_tier_access",
+                    &min_tier,
+                    &ctx.allowed_tiers,
+                ))
+            }
+        }
+        TierRequirement::Max(max_tier) => {
+            if ctx.allowed_tiers.iter().any(|tier| *tier <= max_tier) {
+                Ok(())
+            } else {
+                Err(PolicyError::denied(
+                    ctx,
+                    "tier",
+                    "tier_not_permitted",
+                    &max_tier,
+                    &ctx.allowed_tiers,
+                ))
+            }
+        }
+        TierRequirement::Range { min, max } => {
+            if ctx
                 .allowed_tiers
                 .iter()
-                .map(|tier| tier_rank(*tier))
-                .max()
-                .unwrap_or(0);
-            if max_rank >= required_rank {
+                .any(|tier| (min..=max).contains(tier))
+            {
                 Ok(())
             } else {
-                Err(PolicyError::forbidden("insufficient tier access"))
+                Err(PolicyError::denied(
+                    ctx,
+                    "tier",
+                    "tier_not_permitted",
+                    &(min..=max),
+                    &ctx.allowed_tiers,
+                ))
             }
         }
         TierRequirement::Allowlist(allowed) => {
             if allowed.iter().any(|tier| ctx.allowed_tiers.contains(tier)) {
                 Ok(())
             } else {
-                Err(PolicyError::forbidden("tier not permitted"))
+                Err(PolicyError::denied(
+                    ctx,
+                    "tier",
+                    "tier_not_permitted",
+                    &allowed,
+                    &ctx.allowed_tiers,
+                ))
             }
         }
     }
@@ -76,7 +153,13 @@ pub fn require_scope(ctx: &RequestContext, required_scope: &str) -> Result<(), P
     if ctx.scopes.iter().any(|scope| scope == required_scope) {
         Ok(())
     } else {
-        Err(PolicyError::forbidden("scope not permitted"))
+        Err(PolicyError::denied(
+            ctx,
+            "scope",
+            "scope_not_permitted",
+            &required_scope,
+            &ctx.scopes,
+        ))
     }
 }
 
@@ -95,15 +178,53 @@ pub fn require_scope_any(
     {
         Ok(())
     } else {
-        Err(PolicyError::forbidden("scope not permitted"))
+        Err(PolicyError::denied(
+            ctx,
+            "scope",
+            "scope_not_permitted",
+            &required_scopes,
+            &ctx.scopes,
+        ))
     }
 }
 
-fn tier_rank(tier: SensitivityTier) -> u8 {
-    match tier {
-        SensitivityTier::Green => 0,
-        SensitivityTier::Amber => 1,
-        SensitivityTier::Red => 2,
+/// `lifeready_policy_denials_total{check,reason}` counters, kept in-process so a service
+/// can expose them from whatever metrics endpoint it runs.
+#[cfg(feature = "metrics")]
+pub mod metrics {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    static DENIALS: Mutex<BTreeMap<(&'static str, &'static str), u64>> =
+        Mutex::new(BTreeMap::new());
+
+    pub(crate) fn record_denial(check: &'static str, reason: &'static str) {
+        let mut denials = DENIALS.lock().unwrap_or_else(|error| error.into_inner());
+        *denials.entry((check, reason)).or_default() += 1;
+    }
+
+    /// Denials so far for one `check` (`role`, `tier`, `scope`) and `reason`.
+    pub fn denials_total(check: &str, reason: &str) -> u64 {
+        let denials = DENIALS.lock().unwrap_or_else(|error| error.into_inner());
+        denials
+            .iter()
+            .find(|((c, r), _)| *c == check && *r == reason)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// The counters in Prometheus text exposition format.
+    pub fn render() -> String {
+        let denials = DENIALS.lock().unwrap_or_else(|error| error.into_inner());
+        let mut out = String::from(
+            "# HELP lifeready_policy_denials_total Policy checks that denied a request.\n\
+             # TYPE lifeready_policy_denials_total counter\n",
+        );
+        for ((check, reason), count) in denials.iter() {
+            out.push_str(&format!(
+                "lifeready_policy_denials_total{{check=\"{check}\",reason=\"{reason}\"}} {count}\n"
+            ));
+        }
+        out
     }
 }
 
@@ -122,6 +243,8 @@ mod tests {
             scopes: scopes.into_iter().map(|s| s.to_string()).collect(),
             expires_at: chrono::Utc::now(),
             email: None,
+            acting_for: None,
+            org_id: None,
         }
     }
 
@@ -140,6 +263,11 @@ mod tests {
                 false,
             ),
             (vec![Role::EmergencyContact], vec![Role::Principal], false),
+            (
+                vec![Role::Administrator],
+                vec![Role::Principal, Role::Proxy],
+                false,
+            ),
         ];
 
         for (roles, allowed, ok) in cases {
@@ -149,6 +277,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn has_role_checks_membership() {
+        let admin = ctx(
+            vec![Role::Principal, Role::Administrator],
+            vec![SensitivityTier::Green],
+            vec!["read:all"],
+        );
+        assert!(has_role(&admin, Role::Administrator));
+        assert!(!has_role(&admin, Role::Proxy));
+    }
+
     #[test]
     fn rbac_tier_matrix() {
         let cases = vec![
@@ -166,6 +305,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tiers_order_by_sensitivity() {
+        assert!(SensitivityTier::Green < SensitivityTier::Amber);
+        assert!(SensitivityTier::Amber < SensitivityTier::Red);
+        assert_eq!(
+            [
+                SensitivityTier::Red,
+                SensitivityTier::Green,
+                SensitivityTier::Amber
+            ]
+            .into_iter()
+            .max(),
+            Some(SensitivityTier::Red)
+        );
+    }
+
+    #[test]
+    fn rbac_tier_max_matrix() {
+        let cases = vec![
+            (vec![SensitivityTier::Green], SensitivityTier::Green, true),
+            (vec![SensitivityTier::Amber], SensitivityTier::Green, false),
+            (vec![SensitivityTier::Amber], SensitivityTier::Amber, true),
+            (vec![SensitivityTier::Red], SensitivityTier::Amber, false),
+            (
+                vec![SensitivityTier::Green, SensitivityTier::Red],
+                SensitivityTier::Amber,
+                true,
+            ),
+            (vec![SensitivityTier::Red], SensitivityTier::Red, true),
+            (vec![], SensitivityTier::Red, false),
+        ];
+
+        for (tiers, max, ok) in cases {
+            let ctx = ctx(vec![Role::Principal], tiers, vec!["read:all"]);
+            let result = require_tier(&ctx, TierRequirement::Max(max));
+            assert_eq!(result.is_ok(), ok);
+        }
+    }
+
+    #[test]
+    fn rbac_tier_range_matrix() {
+        let green_to_amber = (SensitivityTier::Green, SensitivityTier::Amber);
+        let amber_only = (SensitivityTier::Amber, SensitivityTier::Amber);
+        let cases = vec![
+            (vec![SensitivityTier::Green], green_to_amber, true),
+            (vec![SensitivityTier::Amber], green_to_amber, true),
+            (vec![SensitivityTier::Red], green_to_amber, false),
+            (vec![SensitivityTier::Green], amber_only, false),
+            (vec![SensitivityTier::Amber], amber_only, true),
+            (vec![SensitivityTier::Red], amber_only, false),
+            (
+                vec![SensitivityTier::Green, SensitivityTier::Red],
+                amber_only,
+                false,
+            ),
+            (
+                vec![SensitivityTier::Amber],
+                (SensitivityTier::Red, SensitivityTier::Green),
+                false,
+            ),
+        ];
+
+        for (tiers, (min, max), ok) in cases {
+            let ctx = ctx(vec![Role::Principal], tiers, vec!["read:all"]);
+            let result = require_tier(&ctx, TierRequirement::Range { min, max });
+            assert_eq!(result.is_ok(), ok);
+        }
+    }
+
     #[test]
     fn rbac_tier_allowlist_matrix() {
         let cases = vec![
@@ -232,4 +440,25 @@ mod tests {
             assert_eq!(result.is_ok(), ok);
         }
     }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn denials_are_counted_by_check_and_reason() {
+        let before = metrics::denials_total("scope", "scope_not_permitted");
+        let ctx = ctx(
+            vec![Role::Principal],
+            vec![SensitivityTier::Green],
+            vec!["read:packs"],
+        );
+        assert!(require_scope(&ctx, "write:limited").is_err());
+        assert!(require_scope(&ctx, "read:packs").is_ok());
+
+        assert_eq!(
+            metrics::denials_total("scope", "scope_not_permitted"),
+            before + 1
+        );
+        assert!(metrics::render().contains(
+            "lifeready_policy_denials_total{check=\"scope\",reason=\"scope_not_permitted\"}"
+        ));
+    }
 }
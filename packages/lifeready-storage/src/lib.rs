@@ -0,0 +1,222 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Prefix marking a blob sealed by [`BlobCipher`]; the 12-byte nonce follows it, then
+/// the AES-256-GCM ciphertext and tag.
+pub const STORAGE_BLOB_MAGIC: &[u8; 4] = b"LRS1";
+pub const STORAGE_BLOB_NONCE_BYTES: usize = 12;
+
+/// AES-256-GCM at-rest encryption for stored blobs, using a fresh random nonce per seal.
+/// Blobs without the [`STORAGE_BLOB_MAGIC`] prefix open as stored, which keeps blobs
+/// written before encryption was enabled (and files clients staged directly on disk)
+/// readable.
+pub struct BlobCipher {
+    cipher: aes_gcm::Aes256Gcm,
+}
+
+impl BlobCipher {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            cipher: aes_gcm::Aes256Gcm::new(key.into()),
+        }
+    }
+
+    pub fn seal(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let nonce = aes_gcm::Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, data)
+            .map_err(|_| io::Error::other("storage encryption failed"))?;
+        let mut sealed = Vec::with_capacity(
+            STORAGE_BLOB_MAGIC.len() + STORAGE_BLOB_NONCE_BYTES + ciphertext.len(),
+        );
+        sealed.extend_from_slice(STORAGE_BLOB_MAGIC);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    pub fn open(&self, stored: Vec<u8>) -> io::Result<Vec<u8>> {
+        let Some(sealed) = stored.strip_prefix(STORAGE_BLOB_MAGIC) else {
+            return Ok(stored);
+        };
+        if sealed.len() < STORAGE_BLOB_NONCE_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "encrypted blob is truncated",
+            ));
+        }
+        let (nonce, ciphertext) = sealed.split_at(STORAGE_BLOB_NONCE_BYTES);
+        self.cipher
+            .decrypt(aes_gcm::Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "encrypted blob failed authentication",
+                )
+            })
+    }
+}
+
+pub fn is_sealed(stored: &[u8]) -> bool {
+    stored.starts_with(STORAGE_BLOB_MAGIC)
+}
+
+/// Copies a stored blob to `dest`, decrypting sealed blobs so the copy carries the
+/// plaintext its recorded hash describes. Sealed blobs without a key fail rather than
+/// copy ciphertext.
+pub fn copy_blob(source: &Path, dest: &Path, key: Option<&[u8; 32]>) -> io::Result<()> {
+    let stored = std::fs::read(source)?;
+    let plaintext = match key {
+        Some(key) => BlobCipher::new(key).open(stored)?,
+        None if is_sealed(&stored) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "blob is encrypted at rest but STORAGE_ENCRYPTION_KEY is not set",
+            ));
+        }
+        None => stored,
+    };
+    std::fs::write(dest, plaintext)
+}
+
+/// Resolves `path` against `root` without following anything: `.` and `..` are applied
+/// lexically, the result must stay under `root`, and no existing component below `root`
+/// may be a symlink, so a linked file or directory cannot reach another tenant's blobs.
+/// Components that do not exist yet are allowed. Returns the path relative to `root`.
+pub fn confined_path(root: &Path, path: &Path) -> Option<PathBuf> {
+    let root = lexically_normalize(&std::path::absolute(root).ok()?);
+    let normalized = lexically_normalize(&std::path::absolute(path).ok()?);
+    let relative = normalized.strip_prefix(&root).ok()?.to_path_buf();
+    let mut current = root;
+    for component in relative.components() {
+        current.push(component);
+        match std::fs::symlink_metadata(&current) {
+            Ok(metadata) if metadata.file_type().is_symlink() => return None,
+            Ok(_) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => break,
+            Err(_) => return None,
+        }
+    }
+    Some(relative)
+}
+
+fn lexically_normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Reads `STORAGE_ENCRYPTION_KEY`, 64 hex characters (32 bytes) for AES-256-GCM.
+/// Unset or blank means blobs are stored and read unencrypted.
+pub fn storage_encryption_key_from_env() -> Result<Option<[u8; 32]>, String> {
+    hex_key_from_env("STORAGE_ENCRYPTION_KEY")
+}
+
+/// Reads a 32-byte key given as 64 hex characters from the environment variable `name`.
+/// Unset or blank is `Ok(None)`.
+pub fn hex_key_from_env(name: &str) -> Result<Option<[u8; 32]>, String> {
+    parse_hex_key(name, std::env::var(name).ok().as_deref())
+}
+
+fn parse_hex_key(name: &str, value: Option<&str>) -> Result<Option<[u8; 32]>, String> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let bytes = hex::decode(value).map_err(|_| format!("{name} must be hex-encoded"))?;
+    let key: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| format!("{name} must be 32 bytes (64 hex characters)"))?;
+    Ok(Some(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_blob_decrypts_sealed_blobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = [7u8; 32];
+        let source = dir.path().join("sealed");
+        std::fs::write(&source, BlobCipher::new(&key).seal(b"red tier").unwrap()).unwrap();
+        let plain_source = dir.path().join("plain");
+        std::fs::write(&plain_source, b"legacy").unwrap();
+        let dest = dir.path().join("out");
+
+        copy_blob(&source, &dest, Some(&key)).unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), b"red tier");
+        copy_blob(&plain_source, &dest, Some(&key)).unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), b"legacy");
+        copy_blob(&plain_source, &dest, None).unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), b"legacy");
+        assert!(copy_blob(&source, &dest, None).is_err());
+        assert!(copy_blob(&source, &dest, Some(&[8u8; 32])).is_err());
+    }
+
+    #[test]
+    fn open_rejects_truncated_and_tampered_blobs() {
+        let cipher = BlobCipher::new(&[7; 32]);
+        let mut sealed = cipher.seal(b"secret").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert_eq!(
+            cipher.open(sealed).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(cipher.open(b"LRS1abc".to_vec()).is_err());
+        assert_eq!(cipher.open(b"plain".to_vec()).unwrap(), b"plain");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn confined_path_rejects_escapes_and_symlinked_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("storage");
+        std::fs::create_dir_all(root.join("orgs/acme")).unwrap();
+        std::fs::create_dir_all(root.join("orgs/other")).unwrap();
+        std::fs::write(root.join("orgs/other/blob"), b"other").unwrap();
+        std::os::unix::fs::symlink(root.join("orgs/other"), root.join("orgs/acme/linked")).unwrap();
+        std::os::unix::fs::symlink(root.join("orgs/other/blob"), root.join("orgs/acme/file"))
+            .unwrap();
+
+        assert_eq!(
+            confined_path(&root, &root.join("orgs/acme/./new/../blob")),
+            Some(PathBuf::from("orgs/acme/blob"))
+        );
+        assert_eq!(
+            confined_path(&root, &root.join("orgs/other/blob")),
+            Some(PathBuf::from("orgs/other/blob"))
+        );
+        assert_eq!(confined_path(&root, &root.join("../outside")), None);
+        assert_eq!(confined_path(&root, Path::new("/etc/passwd")), None);
+        assert_eq!(
+            confined_path(&root, &root.join("orgs/acme/linked/blob")),
+            None
+        );
+        assert_eq!(confined_path(&root, &root.join("orgs/acme/file")), None);
+    }
+
+    #[test]
+    fn hex_key_parses_64_hex_characters() {
+        let parse = |value| parse_hex_key("STORAGE_ENCRYPTION_KEY", value);
+        assert_eq!(parse(None).unwrap(), None);
+        assert_eq!(parse(Some("  ")).unwrap(), None);
+        let key = "ab".repeat(32);
+        assert_eq!(parse(Some(&key)).unwrap(), Some([0xab; 32]));
+        assert_eq!(
+            parse(Some("abcd")).unwrap_err(),
+            "STORAGE_ENCRYPTION_KEY must be 32 bytes (64 hex characters)"
+        );
+        assert!(parse(Some("not hex")).is_err());
+    }
+}
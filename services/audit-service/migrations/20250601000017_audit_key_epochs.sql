@@ -0,0 +1,14 @@
+-- HMAC key epochs for the audit chain. Only key ids live here; secrets come from
+-- AUDIT_HMAC_KEYS. Events appended while an epoch is active record its key_id, and
+-- events from before the first epoch keep plain SHA-256 hashes (key_id NULL).
+CREATE TABLE IF NOT EXISTS key_epochs (
+  key_id text PRIMARY KEY,
+  activated_at timestamptz NOT NULL DEFAULT now(),
+  retired_at timestamptz
+);
+
+-- At most one active epoch at a time.
+CREATE UNIQUE INDEX IF NOT EXISTS key_epochs_single_active
+  ON key_epochs ((retired_at IS NULL)) WHERE retired_at IS NULL;
+
+ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS key_id text REFERENCES key_epochs(key_id);
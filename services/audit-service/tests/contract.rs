@@ -62,6 +62,29 @@ async fn healthz_exists() {
     assert_eq!(res.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn metrics_serves_policy_denials_without_auth() {
+    init_env();
+    let app = audit_service::app();
+    let req = Request::builder()
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers()[header::CONTENT_TYPE],
+        "text/plain; version=0.0.4"
+    );
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(
+        text.contains("# TYPE lifeready_policy_denials_total counter"),
+        "{text}"
+    );
+}
+
 #[tokio::test]
 async fn readyz_exists() {
     init_env();
@@ -150,7 +173,7 @@ async fn append_event_returns_hashes() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -203,7 +226,7 @@ async fn append_event_rejects_invalid_tier() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -243,7 +266,7 @@ async fn append_event_rejects_invalid_actor_id() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -283,7 +306,7 @@ async fn append_event_rejects_invalid_case_id() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -323,7 +346,7 @@ async fn append_event_allows_missing_case_id() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -388,7 +411,7 @@ async fn export_returns_head_hash() {
     let export_dir = unique_dir("audit-export");
     std::fs::create_dir_all(&export_dir).unwrap();
     with_env_async(&[("AUDIT_EXPORT_DIR", export_dir.to_str())], || async {
-        sqlx::query("TRUNCATE audit_events")
+        sqlx::query("TRUNCATE audit_events, key_epochs")
             .execute(&pool)
             .await
             .unwrap();
@@ -434,7 +457,7 @@ async fn export_returns_latest_event_hash() {
     let export_dir = unique_dir("audit-export");
     std::fs::create_dir_all(&export_dir).unwrap();
     with_env_async(&[("AUDIT_EXPORT_DIR", export_dir.to_str())], || async {
-        sqlx::query("TRUNCATE audit_events")
+        sqlx::query("TRUNCATE audit_events, key_epochs")
             .execute(&pool)
             .await
             .unwrap();
@@ -502,7 +525,7 @@ async fn append_event_sets_prev_hash_from_latest_event() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -599,7 +622,7 @@ async fn append_event_rejects_missing_scope() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -639,7 +662,7 @@ async fn export_rejects_missing_scope() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -676,6 +699,17 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     sqlx::query("CREATE EXTENSION IF NOT EXISTS \"uuid-ossp\";")
         .execute(pool)
         .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS revoked_tokens (\
+            jti text PRIMARY KEY,\
+            principal_id text NOT NULL,\
+            expires_at timestamptz NOT NULL,\
+            revoked_by text NOT NULL,\
+            revoked_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
     sqlx::query(
         "DO $$ BEGIN \
          IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'sensitivity_tier') THEN \
@@ -700,6 +734,20 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS key_epochs (\
+            key_id text PRIMARY KEY,\
+            activated_at timestamptz NOT NULL DEFAULT now(),\
+            retired_at timestamptz\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS key_id text REFERENCES key_epochs(key_id);",
+    )
+    .execute(pool)
+    .await?;
     sqlx::query(
         "CREATE INDEX IF NOT EXISTS idx_audit_case_time ON audit_events(case_id, created_at DESC);",
     )
@@ -726,7 +774,7 @@ async fn append_event_rejects_insufficient_role() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -772,7 +820,7 @@ async fn append_event_rejects_insufficient_tier() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -818,7 +866,7 @@ async fn export_rejects_insufficient_role() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -857,7 +905,7 @@ async fn export_rejects_insufficient_tier() {
         Some(pool) => pool,
         None => return,
     };
-    sqlx::query("TRUNCATE audit_events")
+    sqlx::query("TRUNCATE audit_events, key_epochs")
         .execute(&pool)
         .await
         .unwrap();
@@ -888,3 +936,153 @@ async fn export_rejects_insufficient_tier() {
 
     assert_eq!(response.status(), StatusCode::FORBIDDEN);
 }
+
+fn admin_token() -> String {
+    let config = AuthConfig::new("test-secret");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-0000000000ad",
+        Role::Administrator,
+        vec![SensitivityTier::Amber],
+        AccessLevel::LimitedWrite,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
+async fn rotate_key(app: &axum::Router, key_id: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/audit/keys/rotate")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", admin_token()))
+                .body(Body::from(
+                    serde_json::json!({"key_id": key_id}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn rotate_key_opens_keyed_epochs_that_verify() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    let export_dir = unique_dir("audit-export");
+    std::fs::create_dir_all(&export_dir).unwrap();
+    let keys = format!("k1:{},k2:{}", "11".repeat(32), "22".repeat(32));
+    with_env_async(
+        &[
+            ("JWT_SECRET", Some("test-secret")),
+            ("AUDIT_EXPORT_DIR", export_dir.to_str()),
+            ("AUDIT_HMAC_KEYS", Some(&keys)),
+        ],
+        || async {
+            sqlx::query("TRUNCATE audit_events, key_epochs")
+                .execute(&pool)
+                .await
+                .unwrap();
+            let app = audit_service::app();
+
+            let body = serde_json::json!({
+                "actor_principal_id": "00000000-0000-0000-0000-000000000001",
+                "action": "case.export",
+                "tier": "red",
+                "case_id": null,
+                "payload": {"ok": true}
+            })
+            .to_string();
+            let append = |app: axum::Router, body: String| async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method("POST")
+                            .uri("/v1/audit/events")
+                            .header("content-type", "application/json")
+                            .header("authorization", format!("Bearer {}", test_token()))
+                            .body(Body::from(body))
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::CREATED);
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            };
+
+            let legacy = append(app.clone(), body.clone()).await;
+            assert!(legacy.get("key_id").is_none());
+
+            let (status, rotated) = rotate_key(&app, "k1").await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(rotated["key_id"], "k1");
+            assert!(rotated["previous_key_id"].is_null());
+
+            let keyed = append(app.clone(), body.clone()).await;
+            assert_eq!(keyed["key_id"], "k1");
+
+            let (status, rotated) = rotate_key(&app, "k2").await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(rotated["previous_key_id"], "k1");
+            append(app.clone(), body).await;
+
+            let (status, _) = rotate_key(&app, "k1").await;
+            assert_eq!(status, StatusCode::CONFLICT);
+            let (status, _) = rotate_key(&app, "k3").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let active: Vec<String> =
+                sqlx::query_scalar("SELECT key_id FROM key_epochs WHERE retired_at IS NULL")
+                    .fetch_all(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(active, vec!["k2".to_string()]);
+
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri("/v1/audit/export")
+                        .header("authorization", format!("Bearer {}", read_only_token()))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let export_path = PathBuf::from(
+                value["download_url"]
+                    .as_str()
+                    .unwrap()
+                    .trim_start_matches("file://"),
+            );
+            let head = value["head_hash"].as_str().unwrap();
+
+            let keyring = audit_verifier::AuditKeyring::parse(&keys).unwrap();
+            assert_eq!(
+                audit_verifier::verify_audit_chain_with_keys(&export_path, Some(head), &keyring)
+                    .unwrap(),
+                head
+            );
+            assert!(audit_verifier::verify_audit_chain(&export_path, Some(head)).is_err());
+
+            sqlx::query("TRUNCATE audit_events, key_epochs")
+                .execute(&pool)
+                .await
+                .unwrap();
+        },
+    )
+    .await;
+}
@@ -0,0 +1,7 @@
+-- Soft-archive for cases. Rows are never hard-deleted; archived_at hides a case
+-- from listings while keeping its evidence, artifacts, and audit trail intact.
+
+ALTER TABLE cases ADD COLUMN IF NOT EXISTS archived_at timestamptz;
+
+CREATE INDEX IF NOT EXISTS idx_cases_principal_active
+  ON cases(principal_id, created_at DESC) WHERE archived_at IS NULL;
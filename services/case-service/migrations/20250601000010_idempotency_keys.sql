@@ -0,0 +1,12 @@
+-- Idempotency keys for case creation. A retried create with the same key replays
+-- the original case instead of inserting a duplicate; keys are reaped after 24h.
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+  key text NOT NULL,
+  principal_id uuid NOT NULL,
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY (principal_id, key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys(created_at);
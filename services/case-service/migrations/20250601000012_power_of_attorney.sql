@@ -0,0 +1,11 @@
+-- Power of attorney preparation packs (South Africa)
+ALTER TYPE case_type ADD VALUE IF NOT EXISTS 'power_of_attorney_sa';
+
+CREATE TABLE power_of_attorney_cases (
+  case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,
+  principal_person_id uuid NOT NULL,
+  attorney_person_id uuid NOT NULL,
+  powers_scope text NOT NULL CHECK (powers_scope IN ('general', 'special')),
+  required_evidence_slots text[] NOT NULL,
+  notes text
+);
@@ -0,0 +1,3 @@
+-- Optimistic concurrency: every status change or revision bumps the case version,
+-- and writers only apply when the version they read is still current.
+ALTER TABLE cases ADD COLUMN IF NOT EXISTS version bigint NOT NULL DEFAULT 0;
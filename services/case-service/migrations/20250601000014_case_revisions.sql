@@ -0,0 +1,63 @@
+-- Append-only revision history for every case type (POPIA incidents already use
+-- incident_revisions). Each PATCH inserts one row; existing rows are never updated.
+CREATE TABLE IF NOT EXISTS emergency_pack_revisions (
+  revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  revision_number int NOT NULL,
+  notes text,
+  actor_principal_id uuid NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  UNIQUE(case_id, revision_number)
+);
+
+CREATE TABLE IF NOT EXISTS mhca39_revisions (
+  revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  revision_number int NOT NULL,
+  relationship_to_subject text,
+  notes text,
+  actor_principal_id uuid NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  UNIQUE(case_id, revision_number)
+);
+
+CREATE TABLE IF NOT EXISTS will_prep_revisions (
+  revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  revision_number int NOT NULL,
+  notes text,
+  actor_principal_id uuid NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  UNIQUE(case_id, revision_number)
+);
+
+CREATE TABLE IF NOT EXISTS power_of_attorney_revisions (
+  revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  revision_number int NOT NULL,
+  notes text,
+  actor_principal_id uuid NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  UNIQUE(case_id, revision_number)
+);
+
+CREATE TABLE IF NOT EXISTS deceased_estate_revisions (
+  revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  revision_number int NOT NULL,
+  estimated_estate_value_zar numeric,
+  notes text,
+  actor_principal_id uuid NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  UNIQUE(case_id, revision_number)
+);
+
+CREATE TABLE IF NOT EXISTS death_readiness_revisions (
+  revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  revision_number int NOT NULL,
+  notes text,
+  actor_principal_id uuid NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now(),
+  UNIQUE(case_id, revision_number)
+);
@@ -0,0 +1,24 @@
+-- Outbound webhook subscriptions for case status changes. The secret is the HMAC key
+-- for X-LifeReady-Signature, so it is stored as issued rather than hashed.
+CREATE TABLE IF NOT EXISTS webhooks (
+  webhook_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  principal_id uuid NOT NULL,
+  url text NOT NULL,
+  secret text NOT NULL,
+  events text[] NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS webhooks_principal_idx ON webhooks(principal_id);
+
+-- Deliveries that exhausted their retries. webhook_id is deliberately not a foreign key
+-- so the log survives the subscription being deleted.
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+  dead_letter_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  webhook_id uuid NOT NULL,
+  event text NOT NULL,
+  payload jsonb NOT NULL,
+  attempts int NOT NULL,
+  last_error text NOT NULL,
+  created_at timestamptz NOT NULL DEFAULT now()
+);
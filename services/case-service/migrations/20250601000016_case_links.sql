@@ -0,0 +1,12 @@
+-- Directed cross-references between cases owned by the same principal, e.g. a
+-- deceased-estate case derived_from the decedent's earlier will-prep case.
+CREATE TABLE IF NOT EXISTS case_links (
+  from_case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  to_case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  relationship text NOT NULL CHECK (relationship IN ('supersedes', 'derived_from', 'references')),
+  created_at timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY (from_case_id, to_case_id),
+  CHECK (from_case_id <> to_case_id)
+);
+
+CREATE INDEX IF NOT EXISTS case_links_to_case_idx ON case_links(to_case_id);
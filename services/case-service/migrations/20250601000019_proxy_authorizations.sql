@@ -0,0 +1,12 @@
+-- Which principals a proxy may act for, and with which scopes. A proxy token naming a
+-- subject in `acting_for` is only honoured while a row here covers the scope in use.
+CREATE TABLE IF NOT EXISTS proxy_authorizations (
+  proxy_principal_id uuid NOT NULL,
+  subject_principal_id uuid NOT NULL,
+  scopes text[] NOT NULL DEFAULT ARRAY[]::text[],
+  granted_at timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY (proxy_principal_id, subject_principal_id),
+  CHECK (proxy_principal_id <> subject_principal_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_proxy_authorizations_subject ON proxy_authorizations(subject_principal_id);
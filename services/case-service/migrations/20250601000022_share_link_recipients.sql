@@ -0,0 +1,7 @@
+-- A share link may be bound to one recipient, who must then present a one-time code sent
+-- to that address. Codes are stored as sha256("<token>:<code>"); wrong guesses accumulate
+-- in share_link_otp_attempts until the owner re-issues the link.
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_recipient_email text;
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_otp_sha256 text;
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_otp_expires_at timestamptz;
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_otp_attempts integer NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Per-link usage counters, reset whenever the link is re-issued. A link with
+-- share_link_max_accesses set expires as soon as its access count reaches the cap.
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_access_count integer NOT NULL DEFAULT 0;
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_last_accessed_at timestamptz;
+ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS share_link_max_accesses integer CHECK (share_link_max_accesses > 0);
@@ -0,0 +1,6 @@
+-- Optional version pin for attached evidence. When set, exports use this document
+-- version instead of the latest one, so later uploads don't change the bundle.
+ALTER TABLE mhca39_evidence ADD COLUMN IF NOT EXISTS version_id uuid
+  REFERENCES document_versions(version_id);
+ALTER TABLE case_evidence ADD COLUMN IF NOT EXISTS version_id uuid
+  REFERENCES document_versions(version_id);
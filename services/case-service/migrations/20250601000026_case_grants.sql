@@ -0,0 +1,12 @@
+-- Read access to a case for someone other than its owner, e.g. the executor nominee
+-- of a death-readiness or will-prep case. A grant only applies while the grantee's
+-- token carries the granted role.
+CREATE TABLE IF NOT EXISTS case_grants (
+  case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,
+  grantee_principal_id uuid NOT NULL,
+  role text NOT NULL CHECK (role IN ('executor_nominee')),
+  granted_at timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY (case_id, grantee_principal_id, role)
+);
+
+CREATE INDEX IF NOT EXISTS case_grants_grantee_idx ON case_grants(grantee_principal_id);
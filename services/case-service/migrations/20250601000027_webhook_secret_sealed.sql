@@ -0,0 +1,4 @@
+-- Webhook secrets are HMAC keys, so they are sealed with WEBHOOK_SECRET_KEY (AES-256-GCM,
+-- the same LRS1 format as vault blobs) rather than hashed. Existing plaintext secrets
+-- carry over as bytes and are sealed on their next delivery.
+ALTER TABLE webhooks ALTER COLUMN secret TYPE bytea USING convert_to(secret, 'UTF8');
@@ -0,0 +1,4 @@
+-- Multi-case bundles are recorded as one artifact row per bundled case, sharing the
+-- bundle's id, so a bundle download can check access to every case it contains.
+ALTER TABLE case_artifacts ADD COLUMN IF NOT EXISTS bundle_id uuid;
+CREATE INDEX IF NOT EXISTS idx_artifacts_bundle ON case_artifacts(bundle_id) WHERE bundle_id IS NOT NULL;
@@ -1,6 +1,4 @@
 use axum::Router;
-use std::future::{Future, pending};
-use tokio::net::TcpListener;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -8,12 +6,32 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 async fn main() {
     init_tracing("case_service=info,tower_http=info");
 
-    let _db = case_service::check_db().await;
+    let pool = case_service::check_db().await;
+    let audit_keys = std::sync::Arc::new(
+        lifeready_audit::AuditKeyring::from_env().expect("AUDIT_HMAC_KEYS misconfigured"),
+    );
+    if let Some(pool) = &pool {
+        tokio::spawn(case_service::run_link_reaper(
+            pool.clone(),
+            audit_keys.clone(),
+            case_service::link_reaper_interval_from_env(),
+        ));
+    }
+    if let Some(max_age) = case_service::export_retention_from_env() {
+        tokio::spawn(case_service::run_export_gc(
+            pool,
+            audit_keys,
+            case_service::export_dir_from_env(),
+            max_age,
+            case_service::export_gc_interval_from_env(),
+        ));
+    }
     let addr = case_service::addr_from_env(8084);
 
     tracing::info!(%addr, "case-service listening");
-    let listener = TcpListener::bind(addr).await.unwrap();
-    run_with_listener(listener, pending()).await.unwrap();
+    lifeready_auth::serve_with_shutdown(build_app(), addr)
+        .await
+        .unwrap();
 }
 
 fn init_tracing(default_filter: &str) {
@@ -30,15 +48,6 @@ fn build_app() -> Router {
     case_service::router().layer(TraceLayer::new_for_http())
 }
 
-async fn run_with_listener<F>(listener: TcpListener, shutdown: F) -> std::io::Result<()>
-where
-    F: Future<Output = ()> + Send + 'static,
-{
-    axum::serve(listener, build_app())
-        .with_graceful_shutdown(shutdown)
-        .await
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,17 +55,24 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
     };
+    use std::time::Duration;
+    use tokio::net::TcpListener;
     use tokio::sync::oneshot;
     use tower::util::ServiceExt;
 
     #[tokio::test]
-    async fn run_with_listener_stops_on_shutdown() {
+    async fn serve_stops_on_shutdown() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let (tx, rx) = oneshot::channel::<()>();
         let handle = tokio::spawn(async move {
-            run_with_listener(listener, async move {
-                let _ = rx.await;
-            })
+            lifeready_auth::serve_listener_with_shutdown(
+                listener,
+                build_app(),
+                async move {
+                    let _ = rx.await;
+                },
+                Duration::from_secs(1),
+            )
             .await
         });
         let _ = tx.send(());
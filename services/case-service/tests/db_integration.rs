@@ -21,6 +21,8 @@ fn init_env() {
     unsafe {
         std::env::set_var("LIFEREADY_ENV", "dev");
         std::env::set_var("JWT_SECRET", "test-secret-32-chars-minimum!!");
+        // The webhook receivers in these tests listen on loopback over plain http.
+        std::env::set_var("WEBHOOK_ALLOW_INTERNAL_TARGETS", "true");
     }
 }
 
@@ -63,6 +65,62 @@ fn token_other_principal() -> String {
     config.issue_token(&claims).expect("token")
 }
 
+fn token_other_principal_write() -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-000000000999",
+        Role::Principal,
+        vec![SensitivityTier::Amber],
+        AccessLevel::LimitedWrite,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
+fn token_other_administrator() -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-000000000999",
+        Role::Administrator,
+        vec![SensitivityTier::Amber],
+        AccessLevel::ReadOnlyAll,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
+fn token_other_executor_nominee() -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-000000000999",
+        Role::ExecutorNominee,
+        vec![SensitivityTier::Amber],
+        AccessLevel::ReadOnlyAll,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
+/// Proxy token (principal …0777) holding read and write scopes, optionally acting for
+/// another principal.
+fn token_proxy(acting_for: Option<&str>) -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let mut claims = Claims::new(
+        "00000000-0000-0000-0000-000000000777",
+        Role::Proxy,
+        vec![SensitivityTier::Amber],
+        AccessLevel::LimitedWrite,
+        None,
+        300,
+    );
+    claims.scopes.push("read:all".into());
+    claims.acting_for = acting_for.map(str::to_string);
+    config.issue_token(&claims).expect("token")
+}
+
 fn token_read_packs() -> String {
     let config = AuthConfig::new("test-secret-32-chars-minimum!!");
     let claims = Claims::new(
@@ -106,6 +164,17 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     sqlx::query("CREATE EXTENSION IF NOT EXISTS \"uuid-ossp\";")
         .execute(pool)
         .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS revoked_tokens (\
+            jti text PRIMARY KEY,\
+            principal_id text NOT NULL,\
+            expires_at timestamptz NOT NULL,\
+            revoked_by text NOT NULL,\
+            revoked_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
     sqlx::query(
         "DO $$ BEGIN \
          IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'sensitivity_tier') THEN \
@@ -154,6 +223,24 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await
     .ok();
+    sqlx::query(
+        "DO $$ BEGIN \
+         ALTER TYPE case_type ADD VALUE IF NOT EXISTS 'power_of_attorney_sa'; \
+         EXCEPTION WHEN duplicate_object THEN NULL; \
+         END $$;",
+    )
+    .execute(pool)
+    .await
+    .ok();
+    sqlx::query(
+        "DO $$ BEGIN \
+         ALTER TYPE case_type ADD VALUE IF NOT EXISTS 'popia_incident'; \
+         EXCEPTION WHEN duplicate_object THEN NULL; \
+         END $$;",
+    )
+    .execute(pool)
+    .await
+    .ok();
     sqlx::query(
         "DO $$ BEGIN \
          IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'case_status') THEN \
@@ -163,6 +250,20 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    for status in [
+        "evidence_collecting",
+        "draft_generated",
+        "awaiting_oath",
+        "link_issued",
+        "accessed",
+        "expired",
+    ] {
+        sqlx::query(&format!(
+            "ALTER TYPE case_status ADD VALUE IF NOT EXISTS '{status}'"
+        ))
+        .execute(pool)
+        .await?;
+    }
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS cases (\
             case_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
@@ -175,6 +276,25 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    // Estate-service tables the readiness score reads; columns the score ignores are left out.
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS role_grants (\
+            grant_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            principal_id uuid NOT NULL,\
+            person_id uuid NOT NULL,\
+            role text NOT NULL,\
+            status text NOT NULL DEFAULT 'invited',\
+            expires_at timestamptz\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query("ALTER TABLE cases ADD COLUMN IF NOT EXISTS archived_at timestamptz;")
+        .execute(pool)
+        .await?;
+    sqlx::query("ALTER TABLE cases ADD COLUMN IF NOT EXISTS version bigint NOT NULL DEFAULT 0;")
+        .execute(pool)
+        .await?;
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS case_artifacts (\
             artifact_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
@@ -187,6 +307,9 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query("ALTER TABLE case_artifacts ADD COLUMN IF NOT EXISTS bundle_id uuid;")
+        .execute(pool)
+        .await?;
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS mhca39_cases (\
             case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
@@ -224,6 +347,9 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query("ALTER TABLE documents ADD COLUMN IF NOT EXISTS deleted_at timestamptz;")
+        .execute(pool)
+        .await?;
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS document_versions (\
             version_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
@@ -253,6 +379,20 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS key_epochs (\
+            key_id text PRIMARY KEY,\
+            activated_at timestamptz NOT NULL DEFAULT now(),\
+            retired_at timestamptz\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS key_id text REFERENCES key_epochs(key_id);",
+    )
+    .execute(pool)
+    .await?;
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS case_evidence (\
             evidence_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
@@ -265,6 +405,14 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    for table in ["mhca39_evidence", "case_evidence"] {
+        sqlx::query(&format!(
+            "ALTER TABLE {table} ADD COLUMN IF NOT EXISTS version_id uuid \
+             REFERENCES document_versions(version_id);"
+        ))
+        .execute(pool)
+        .await?;
+    }
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS will_prep_cases (\
             case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
@@ -275,6 +423,58 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS power_of_attorney_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            principal_person_id uuid NOT NULL,\
+            attorney_person_id uuid NOT NULL,\
+            powers_scope text NOT NULL CHECK (powers_scope IN ('general', 'special')),\
+            required_evidence_slots text[] NOT NULL,\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS death_readiness_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            executor_nominee_person_id uuid NOT NULL,\
+            asset_document_ids uuid[] NOT NULL DEFAULT ARRAY[]::uuid[],\
+            contact_document_ids uuid[] NOT NULL DEFAULT ARRAY[]::uuid[],\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    for table in ["will_prep_revisions", "power_of_attorney_revisions"] {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (\
+                revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+                case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+                revision_number int NOT NULL,\
+                notes text,\
+                actor_principal_id uuid NOT NULL,\
+                created_at timestamptz NOT NULL DEFAULT now(),\
+                UNIQUE(case_id, revision_number)\
+            );"
+        ))
+        .execute(pool)
+        .await?;
+    }
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS deceased_estate_revisions (\
+            revision_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+            revision_number int NOT NULL,\
+            estimated_estate_value_zar numeric,\
+            notes text,\
+            actor_principal_id uuid NOT NULL,\
+            created_at timestamptz NOT NULL DEFAULT now(),\
+            UNIQUE(case_id, revision_number)\
+        );",
+    )
+    .execute(pool)
+    .await?;
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS deceased_estate_cases (\
             case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
@@ -287,12 +487,138 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS popia_incident_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            incident_title text NOT NULL,\
+            description text,\
+            affected_data_classes text[] NOT NULL DEFAULT ARRAY[]::text[],\
+            affected_user_count int,\
+            mitigation_steps text,\
+            reported_at timestamptz NOT NULL DEFAULT now(),\
+            required_evidence_slots text[] NOT NULL DEFAULT ARRAY[]::text[],\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS emergency_pack_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            directive_document_ids uuid[] NOT NULL DEFAULT ARRAY[]::uuid[],\
+            emergency_contacts jsonb NOT NULL DEFAULT '[]'::jsonb,\
+            share_link_token text,\
+            share_link_expires_at timestamptz,\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    for column in [
+        "share_link_recipient_email text",
+        "share_link_otp_sha256 text",
+        "share_link_otp_expires_at timestamptz",
+        "share_link_otp_attempts integer NOT NULL DEFAULT 0",
+        "share_link_access_count integer NOT NULL DEFAULT 0",
+        "share_link_last_accessed_at timestamptz",
+        "share_link_max_accesses integer CHECK (share_link_max_accesses > 0)",
+    ] {
+        sqlx::query(&format!(
+            "ALTER TABLE emergency_pack_cases ADD COLUMN IF NOT EXISTS {column};"
+        ))
+        .execute(pool)
+        .await?;
+    }
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS case_transitions (\
+            transition_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+            from_status text NOT NULL,\
+            to_status text NOT NULL,\
+            actor_principal_id uuid NOT NULL,\
+            reason text,\
+            created_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS idempotency_keys (\
+            key text NOT NULL,\
+            principal_id uuid NOT NULL,\
+            case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+            created_at timestamptz NOT NULL DEFAULT now(),\
+            PRIMARY KEY (principal_id, key)\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS case_links (\
+            from_case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+            to_case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+            relationship text NOT NULL CHECK (relationship IN ('supersedes', 'derived_from', 'references')),\
+            created_at timestamptz NOT NULL DEFAULT now(),\
+            PRIMARY KEY (from_case_id, to_case_id),\
+            CHECK (from_case_id <> to_case_id)\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS case_grants (\
+            case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+            grantee_principal_id uuid NOT NULL,\
+            role text NOT NULL CHECK (role IN ('executor_nominee')),\
+            granted_at timestamptz NOT NULL DEFAULT now(),\
+            PRIMARY KEY (case_id, grantee_principal_id, role)\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS proxy_authorizations (\
+            proxy_principal_id uuid NOT NULL,\
+            subject_principal_id uuid NOT NULL,\
+            scopes text[] NOT NULL DEFAULT ARRAY[]::text[],\
+            granted_at timestamptz NOT NULL DEFAULT now(),\
+            PRIMARY KEY (proxy_principal_id, subject_principal_id),\
+            CHECK (proxy_principal_id <> subject_principal_id)\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS webhooks (\
+            webhook_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            principal_id uuid NOT NULL,\
+            url text NOT NULL,\
+            secret bytea NOT NULL,\
+            events text[] NOT NULL,\
+            created_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS webhook_dead_letters (\
+            dead_letter_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            webhook_id uuid NOT NULL,\
+            event text NOT NULL,\
+            payload jsonb NOT NULL,\
+            attempts int NOT NULL,\
+            last_error text NOT NULL,\
+            created_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
     Ok(())
 }
 
 async fn reset_db(pool: &PgPool) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "TRUNCATE audit_events, document_versions, documents, mhca39_evidence, mhca39_cases, case_evidence, will_prep_cases, deceased_estate_cases, case_artifacts, cases RESTART IDENTITY CASCADE",
+        "TRUNCATE audit_events, key_epochs, document_versions, documents, mhca39_evidence, mhca39_cases, case_evidence, will_prep_cases, power_of_attorney_cases, death_readiness_cases, deceased_estate_cases, popia_incident_cases, will_prep_revisions, power_of_attorney_revisions, deceased_estate_revisions, emergency_pack_cases, case_transitions, case_artifacts, idempotency_keys, case_links, case_grants, role_grants, proxy_authorizations, webhooks, webhook_dead_letters, cases RESTART IDENTITY CASCADE",
     )
         .execute(pool)
         .await?;
@@ -307,6 +633,19 @@ fn unique_dir(name: &str) -> PathBuf {
     std::env::temp_dir().join(format!("{name}-{}-{}", std::process::id(), nanos))
 }
 
+/// Staging directory of an unencrypted export, next to the archive named in `archive_url`.
+fn export_bundle_dir(value: &serde_json::Value) -> PathBuf {
+    let archive = value["archive_url"]
+        .as_str()
+        .and_then(|url| url.strip_prefix("file://"))
+        .unwrap();
+    let bundle = archive
+        .strip_suffix(".zip")
+        .or_else(|| archive.strip_suffix(".tar.gz"))
+        .unwrap();
+    PathBuf::from(bundle)
+}
+
 #[tokio::test]
 async fn create_emergency_pack_persists_case() {
     init_env();
@@ -318,7 +657,7 @@ async fn create_emergency_pack_persists_case() {
 
     let app = case_service::router();
     let body =
-        serde_json::json!({"directive_document_ids": [], "emergency_contacts": []}).to_string();
+        serde_json::json!({"directive_document_ids": [], "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}]}).to_string();
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -347,6 +686,74 @@ async fn create_emergency_pack_persists_case() {
     assert_eq!(case_type, "emergency_pack");
 }
 
+#[tokio::test]
+async fn create_emergency_pack_replays_idempotency_key() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let app = case_service::router();
+    let body =
+        serde_json::json!({"directive_document_ids": [], "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}]}).to_string();
+    let mut case_ids = Vec::new();
+    for expected in [StatusCode::CREATED, StatusCode::OK] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/cases/emergency-pack")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .header("idempotency-key", "retry-1")
+                    .body(Body::from(body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+        let location = response
+            .headers()
+            .get("location")
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let case_id = value["case_id"].as_str().unwrap().to_string();
+        if expected == StatusCode::CREATED {
+            assert_eq!(location, Some(format!("/v1/cases/{case_id}")));
+        } else {
+            assert_eq!(location, None);
+        }
+        case_ids.push(case_id);
+    }
+    assert_eq!(case_ids[0], case_ids[1]);
+
+    // The Location header resolves to the case it names.
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{}", case_ids[0]))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["case_id"], case_ids[0].as_str());
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM cases")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
 #[tokio::test]
 async fn create_mhca39_persists_case() {
     init_env();
@@ -362,7 +769,8 @@ async fn create_mhca39_persists_case() {
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
         "relationship_to_subject": "spouse",
         "notes": "test",
-        "required_evidence_slots": ["id", "letter"]
+        "required_evidence_slots": ["id", "letter"],
+        "allow_custom_slots": true
     })
     .to_string();
 
@@ -420,7 +828,8 @@ async fn attach_evidence_and_export_case() {
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
         "relationship_to_subject": "spouse",
         "notes": "test",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
 
@@ -486,7 +895,7 @@ async fn attach_evidence_and_export_case() {
         .unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 
-    let response = axum::Router::into_service(app)
+    let response = axum::Router::into_service(app.clone())
         .oneshot(
             Request::builder()
                 .method("POST")
@@ -501,20 +910,63 @@ async fn attach_evidence_and_export_case() {
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    let download_url = value.get("download_url").and_then(|v| v.as_str()).unwrap();
+    let export_path = export_bundle_dir(&value);
     let manifest_sha = value
         .get("manifest_sha256")
         .and_then(|v| v.as_str())
         .unwrap();
     assert_eq!(manifest_sha.len(), 64);
 
-    let export_path = download_url.trim_start_matches("file://");
-    let manifest_path = PathBuf::from(export_path).join("manifest.json");
+    let manifest_path = export_path.join("manifest.json");
     assert!(manifest_path.exists());
+
+    // The bundle is fetched over HTTP from the artifact route named in download_url.
+    let download_url = value["download_url"].as_str().unwrap();
+    let download_path = &download_url[download_url.find("/v1/").unwrap()..];
+    assert!(download_path.starts_with(&format!("/v1/cases/{case_id}/export/")));
+    let download = |uri: String| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let response = download(download_path.to_string()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/zip");
+    assert!(
+        response.headers()["content-disposition"]
+            .to_str()
+            .unwrap()
+            .ends_with(".zip\"")
+    );
+    let bundle = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        sha256_bytes(&bundle),
+        value["archive_sha256"].as_str().unwrap()
+    );
+
+    let response = download(format!("/v1/cases/{case_id}/export/{}", Uuid::new_v4()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // A bundle altered on disk no longer matches its recorded sha256.
+    let archive_path = value["archive_url"]
+        .as_str()
+        .unwrap()
+        .strip_prefix("file://")
+        .unwrap();
+    std::fs::write(archive_path, b"tampered").unwrap();
+    let response = download(download_path.to_string()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
 }
 
 #[tokio::test]
-async fn attach_evidence_rejects_missing_document() {
+async fn attach_evidence_pins_version_for_export() {
     init_env();
     let pool = match setup_db().await {
         Some(pool) => pool,
@@ -523,18 +975,22 @@ async fn attach_evidence_rejects_missing_document() {
     reset_db(&pool).await.unwrap();
 
     let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
     std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
 
     let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
     unsafe {
         std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
     }
 
     let app = case_service::router();
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -552,16 +1008,148 @@ async fn attach_evidence_rejects_missing_document() {
     assert_eq!(response.status(), StatusCode::CREATED);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    let case_id = value.get("case_id").and_then(|v| v.as_str()).unwrap();
+    let case_id = value["case_id"].as_str().unwrap().to_string();
 
-    let missing_doc_id = Uuid::new_v4();
-    let attach_body = serde_json::json!({"document_id": missing_doc_id.to_string()}).to_string();
-    let response = axum::Router::into_service(app)
-        .oneshot(
-            Request::builder()
-                .method("PUT")
-                .uri(format!("/v1/cases/{case_id}/evidence/id"))
-                .header("content-type", "application/json")
+    let document_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'id', 'ID', 'amber', ARRAY[]::text[])",
+    )
+    .bind(document_id)
+    .bind(Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+    let add_version = |name: &str, contents: &'static [u8]| {
+        let blob_path = storage_dir.join(name);
+        std::fs::write(&blob_path, contents).unwrap();
+        let sha256 = sha256_bytes(contents);
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, Uuid>(
+                "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+                 VALUES ($1, $2, $3, $4, 'application/pdf') RETURNING version_id",
+            )
+            .bind(document_id)
+            .bind(format!("file://{}", blob_path.display()))
+            .bind(sha256)
+            .bind(contents.len() as i64)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        }
+    };
+    let pinned_version = add_version("v1", b"first version").await;
+
+    let attach = |body: serde_json::Value| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/id"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+    let response = attach(serde_json::json!({
+        "document_id": document_id.to_string(),
+        "version_id": Uuid::new_v4().to_string(),
+    }))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = attach(serde_json::json!({
+        "document_id": document_id.to_string(),
+        "version_id": pinned_version.to_string(),
+    }))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["version_id"], pinned_version.to_string());
+
+    // A newer upload does not change what the pinned slot exports.
+    add_version("v2", b"second version").await;
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/export"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let manifest: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(export_bundle_dir(&value).join("manifest.json")).unwrap(),
+    )
+    .unwrap();
+    let document = manifest["documents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|doc| doc["slot_name"] == "id")
+        .unwrap();
+    assert_eq!(document["version_id"], pinned_version.to_string());
+    assert_eq!(document["sha256"], sha256_bytes(b"first version"));
+}
+
+#[tokio::test]
+async fn attach_evidence_rejects_missing_document() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+    }
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value.get("case_id").and_then(|v| v.as_str()).unwrap();
+
+    let missing_doc_id = Uuid::new_v4();
+    let attach_body = serde_json::json!({"document_id": missing_doc_id.to_string()}).to_string();
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/id"))
+                .header("content-type", "application/json")
                 .header("authorization", format!("Bearer {}", token_write()))
                 .body(Body::from(attach_body))
                 .unwrap(),
@@ -593,7 +1181,8 @@ async fn export_case_rejects_incomplete_evidence() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id", "letter"]
+        "required_evidence_slots": ["id", "letter"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -613,6 +1202,27 @@ async fn export_case_rejects_incomplete_evidence() {
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
     let case_id = value.get("case_id").and_then(|v| v.as_str()).unwrap();
 
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/cases/{case_id}/export/preflight"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let preflight: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        preflight,
+        serde_json::json!({
+            "exportable": false,
+            "blockers": ["evidence slots incomplete: id, letter"],
+        })
+    );
+
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -626,6 +1236,117 @@ async fn export_case_rejects_incomplete_evidence() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["code"], "evidence_incomplete");
+    assert!(!export_dir.join(case_id).exists());
+}
+
+#[tokio::test]
+async fn deleted_documents_are_neither_attachable_nor_exported() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
+    }
+
+    let app = case_service::router();
+    let case_id = create_mhca39_case(&app, &pool, &storage_dir, true).await;
+    let document_id: Uuid = sqlx::query_scalar(
+        "UPDATE documents SET deleted_at = now() \
+         WHERE document_id = (SELECT document_id FROM mhca39_evidence WHERE case_id = $1::uuid) \
+         RETURNING document_id",
+    )
+    .bind(&case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/cases/{case_id}/export/preflight"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let preflight: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        preflight,
+        serde_json::json!({
+            "exportable": false,
+            "blockers": [format!("document {document_id} has been deleted")],
+        })
+    );
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/export"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["code"], "document_deleted");
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/id"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({ "document_id": document_id }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({
+                        "slots": [{ "slot_name": "id", "document_id": document_id }]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body).contains("id (document not found)"));
 }
 
 #[tokio::test]
@@ -649,7 +1370,8 @@ async fn export_case_rejects_wrong_principal() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -711,7 +1433,8 @@ async fn export_case_rejects_missing_versions() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -794,7 +1517,8 @@ async fn attach_evidence_rejects_unknown_slot() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -910,7 +1634,8 @@ async fn export_case_allows_read_packs_scope() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -1013,7 +1738,8 @@ async fn export_case_includes_audit_events() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -1096,6 +1822,21 @@ async fn export_case_includes_audit_events() {
     .await
     .unwrap();
 
+    let unrelated_event_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO audit_events (event_id, actor_principal_id, action, tier, case_id, payload, prev_hash, event_hash) \
+         VALUES ($1, $2, 'case.export', 'green', $3, $4, $5, $6)",
+    )
+    .bind(unrelated_event_id)
+    .bind(Uuid::parse_str("00000000-0000-0000-0000-000000000002").unwrap())
+    .bind(Uuid::new_v4())
+    .bind(serde_json::json!({"other": true}))
+    .bind(&audit_hash)
+    .bind("c".repeat(64))
+    .execute(&pool)
+    .await
+    .unwrap();
+
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -1111,11 +1852,31 @@ async fn export_case_includes_audit_events() {
     assert_eq!(response.status(), StatusCode::OK);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    let download_url = value.get("download_url").and_then(|v| v.as_str()).unwrap();
-    let export_path = download_url.trim_start_matches("file://");
-    let audit_path = PathBuf::from(export_path).join("audit.jsonl");
+    let audit_path = export_bundle_dir(&value).join("audit.jsonl");
     let audit_contents = std::fs::read_to_string(audit_path).unwrap();
     assert!(audit_contents.contains(&audit_event_id.to_string()));
+    assert!(!audit_contents.contains(&unrelated_event_id.to_string()));
+    for line in audit_contents.lines() {
+        let event: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(event["event"]["case_id"], case_id);
+    }
+
+    // The artifact row, status change and audit event commit together.
+    let row = sqlx::query(
+        "SELECT c.status::text AS status, \
+                (SELECT count(*) FROM case_artifacts a WHERE a.case_id = c.case_id) AS artifacts, \
+                (SELECT e.payload FROM audit_events e \
+                 WHERE e.case_id = c.case_id AND e.action = 'case.exported') AS payload \
+         FROM cases c WHERE c.case_id = $1",
+    )
+    .bind(Uuid::parse_str(case_id).unwrap())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(row.get::<String, _>("status"), "exported");
+    assert_eq!(row.get::<i64, _>("artifacts"), 1);
+    let payload: serde_json::Value = row.get("payload");
+    assert_eq!(payload["manifest_sha256"], value["manifest_sha256"]);
 }
 
 #[tokio::test]
@@ -1139,7 +1900,8 @@ async fn export_case_rejects_missing_blob() {
     let body = serde_json::json!({
         "subject_person_id": "00000000-0000-0000-0000-000000000011",
         "applicant_person_id": "00000000-0000-0000-0000-000000000022",
-        "required_evidence_slots": ["id"]
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
     })
     .to_string();
     let response = axum::Router::into_service(app.clone())
@@ -1213,6 +1975,7 @@ async fn export_case_rejects_missing_blob() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(std::fs::read_dir(&export_dir).unwrap().count(), 0);
 }
 
 #[tokio::test]
@@ -1237,7 +2000,7 @@ async fn create_emergency_pack_rejects_insufficient_role() {
 
     let app = case_service::router();
     let body =
-        serde_json::json!({"directive_document_ids": [], "emergency_contacts": []}).to_string();
+        serde_json::json!({"directive_document_ids": [], "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}]}).to_string();
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -1276,7 +2039,7 @@ async fn create_emergency_pack_rejects_insufficient_tier() {
 
     let app = case_service::router();
     let body =
-        serde_json::json!({"directive_document_ids": [], "emergency_contacts": []}).to_string();
+        serde_json::json!({"directive_document_ids": [], "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}]}).to_string();
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -1315,7 +2078,7 @@ async fn create_emergency_pack_rejects_readonly_scope() {
 
     let app = case_service::router();
     let body =
-        serde_json::json!({"directive_document_ids": [], "emergency_contacts": []}).to_string();
+        serde_json::json!({"directive_document_ids": [], "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}]}).to_string();
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -1405,7 +2168,7 @@ async fn create_emergency_pack_rejects_invalid_principal_id() {
 
     let app = case_service::router();
     let body =
-        serde_json::json!({"directive_document_ids": [], "emergency_contacts": []}).to_string();
+        serde_json::json!({"directive_document_ids": [], "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}]}).to_string();
     let response = axum::Router::into_service(app)
         .oneshot(
             Request::builder()
@@ -1962,22 +2725,21 @@ async fn will_prep_sa_attach_and_export() {
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    let download_url = value.get("download_url").and_then(|v| v.as_str()).unwrap();
-    let bundle_path = download_url.strip_prefix("file://").unwrap();
+    let bundle_path = export_bundle_dir(&value);
 
-    let manifest_path = std::path::Path::new(bundle_path).join("manifest.json");
+    let manifest_path = bundle_path.join("manifest.json");
     let manifest: serde_json::Value =
         serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
     assert_eq!(manifest["case_type"].as_str().unwrap(), "will_prep_sa");
 
-    let instructions_path = std::path::Path::new(bundle_path).join("witnessing_instructions.md");
+    let instructions_path = bundle_path.join("witnessing_instructions.md");
     let instructions = std::fs::read_to_string(&instructions_path).unwrap();
     assert!(instructions.contains("two competent witnesses"));
     assert!(instructions.contains("present simultaneously"));
 }
 
 #[tokio::test]
-async fn deceased_estate_sa_attach_and_export() {
+async fn power_of_attorney_sa_attach_and_export() {
     init_env();
     let pool = match setup_db().await {
         Some(pool) => pool,
@@ -1985,8 +2747,8 @@ async fn deceased_estate_sa_attach_and_export() {
     };
     reset_db(&pool).await.unwrap();
 
-    let storage_dir = unique_dir("estate-storage");
-    let export_dir = unique_dir("estate-exports");
+    let storage_dir = unique_dir("poa-storage");
+    let export_dir = unique_dir("poa-exports");
     std::fs::create_dir_all(&storage_dir).unwrap();
     std::fs::create_dir_all(&export_dir).unwrap();
     unsafe {
@@ -2001,10 +2763,11 @@ async fn deceased_estate_sa_attach_and_export() {
 
     let app = case_service::router();
     let body = serde_json::json!({
-        "deceased_person_id": "00000000-0000-0000-0000-000000000033",
-        "executor_person_id": "00000000-0000-0000-0000-000000000044",
-        "estimated_estate_value_zar": 300000.0,
-        "required_evidence_slots": ["death_certificate"]
+        "principal_person_id": principal_id.to_string(),
+        "attorney_person_id": "00000000-0000-0000-0000-000000000055",
+        "powers_scope": "special",
+        "notes": "sale of erf 123",
+        "required_evidence_slots": ["draft_poa_document"]
     })
     .to_string();
 
@@ -2012,7 +2775,7 @@ async fn deceased_estate_sa_attach_and_export() {
         .oneshot(
             Request::builder()
                 .method("POST")
-                .uri("/v1/cases/deceased-estate-sa")
+                .uri("/v1/cases/power-of-attorney-sa")
                 .header("content-type", "application/json")
                 .header("authorization", format!("Bearer {}", token_write()))
                 .body(Body::from(body))
@@ -2023,38 +2786,42 @@ async fn deceased_estate_sa_attach_and_export() {
     assert_eq!(response.status(), StatusCode::CREATED);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["case_type"], "power_of_attorney_sa");
+    assert_eq!(value["status"], "blocked");
     let case_id = value
         .get("case_id")
         .and_then(|v| v.as_str())
         .unwrap()
         .to_string();
 
+    let slots: Vec<String> = sqlx::query_scalar(
+        "SELECT required_evidence_slots FROM power_of_attorney_cases WHERE case_id = $1",
+    )
+    .bind(Uuid::parse_str(&case_id).unwrap())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(slots, vec!["draft_poa_document".to_string()]);
+
     let document_id = Uuid::new_v4();
     let blob_path = storage_dir.join(format!("{}.bin", document_id));
-    std::fs::write(&blob_path, b"death certificate content").unwrap();
-    let sha256 = sha256_bytes(b"death certificate content");
-
+    std::fs::write(&blob_path, b"draft poa content").unwrap();
     sqlx::query(
-        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) VALUES ($1, $2, $3::document_type, $4, $5::sensitivity_tier, $6)",
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'other', 'Draft POA', 'amber', ARRAY[]::text[])",
     )
     .bind(document_id)
     .bind(principal_id)
-    .bind("other")
-    .bind("Death Certificate")
-    .bind("red")
-    .bind(Vec::<String>::new())
     .execute(&pool)
     .await
     .unwrap();
-
     sqlx::query(
-        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) VALUES ($1, $2, $3, $4, $5)",
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 17, 'application/pdf')",
     )
     .bind(document_id)
     .bind(format!("file://{}", blob_path.display()))
-    .bind(&sha256)
-    .bind(25_i64)
-    .bind("application/pdf")
+    .bind(sha256_bytes(b"draft poa content"))
     .execute(&pool)
     .await
     .unwrap();
@@ -2064,7 +2831,7 @@ async fn deceased_estate_sa_attach_and_export() {
         .oneshot(
             Request::builder()
                 .method("PUT")
-                .uri(format!("/v1/cases/{case_id}/evidence/death_certificate"))
+                .uri(format!("/v1/cases/{case_id}/evidence/draft_poa_document"))
                 .header("content-type", "application/json")
                 .header("authorization", format!("Bearer {}", token_write()))
                 .body(Body::from(attach_body))
@@ -2089,19 +2856,3804 @@ async fn deceased_estate_sa_attach_and_export() {
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    let download_url = value.get("download_url").and_then(|v| v.as_str()).unwrap();
-    let bundle_path = download_url.strip_prefix("file://").unwrap();
+    let bundle_path = export_bundle_dir(&value);
 
-    let manifest_path = std::path::Path::new(bundle_path).join("manifest.json");
     let manifest: serde_json::Value =
-        serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+        serde_json::from_slice(&std::fs::read(bundle_path.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["case_type"], "power_of_attorney_sa");
+
+    let template: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(bundle_path.join("power_of_attorney_draft.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(template["powers_scope"], "special");
     assert_eq!(
-        manifest["case_type"].as_str().unwrap(),
-        "deceased_estate_reporting_sa"
+        template["attorney_person_id"],
+        "00000000-0000-0000-0000-000000000055"
     );
 
-    let instructions_path = std::path::Path::new(bundle_path).join("instructions.md");
-    let instructions = std::fs::read_to_string(&instructions_path).unwrap();
-    assert!(instructions.contains("Letters of Executorship"));
-    assert!(instructions.contains("Letters of Authority"));
+    let instructions =
+        std::fs::read_to_string(bundle_path.join("poa_signing_instructions.md")).unwrap();
+    assert!(instructions.contains("special power of attorney"));
+    assert!(instructions.contains("DISCLAIMER"));
+}
+
+#[tokio::test]
+async fn will_prep_sa_export_renders_pdf_instructions() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("will-pdf-storage");
+    let export_dir = unique_dir("will-pdf-exports");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+    unsafe {
+        std::env::set_var(
+            "LOCAL_STORAGE_DIR",
+            storage_dir.to_string_lossy().to_string(),
+        );
+        std::env::set_var("LOCAL_EXPORT_DIR", export_dir.to_string_lossy().to_string());
+    }
+
+    let principal_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ($1, 'will_prep_sa', 'ready', ARRAY[]::text[]) RETURNING case_id",
+    )
+    .bind(principal_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO will_prep_cases (case_id, principal_person_id, required_evidence_slots) \
+         VALUES ($1, $2, ARRAY['draft_will_document'])",
+    )
+    .bind(case_id)
+    .bind(principal_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let document_id = Uuid::new_v4();
+    let blob_path = storage_dir.join(format!("{}.bin", document_id));
+    std::fs::write(&blob_path, b"draft will content").unwrap();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'will', 'Draft Will', 'amber', ARRAY[]::text[])",
+    )
+    .bind(document_id)
+    .bind(principal_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 18, 'application/pdf')",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256_bytes(b"draft will content"))
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO case_evidence (case_id, slot_name, document_id) \
+         VALUES ($1, 'draft_will_document', $2)",
+    )
+    .bind(case_id)
+    .bind(document_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let response = axum::Router::into_service(case_service::router())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/export?format=pdf"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let bundle_path = export_bundle_dir(&value);
+
+    assert!(bundle_path.join("witnessing_instructions.md").exists());
+    let pdf = std::fs::read(bundle_path.join("witnessing_instructions.pdf")).unwrap();
+    assert!(pdf.starts_with(b"%PDF-1.4"));
+    let pdf_text = String::from_utf8_lossy(&pdf);
+    let disclaimer_at = pdf_text.find("DISCLAIMER").unwrap();
+    assert!(disclaimer_at < pdf_text.find("Manifest Summary").unwrap());
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(bundle_path.join("manifest.json")).unwrap()).unwrap();
+    let pdf_entry = manifest["documents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|doc| doc["bundle_path"] == "witnessing_instructions.pdf")
+        .unwrap();
+    assert_eq!(pdf_entry["sha256"], sha256_bytes(&pdf).as_str());
+    assert!(pdf_entry.get("version_id").is_none());
+    assert_eq!(manifest["schema_version"], 4);
+    assert_eq!(manifest["audit_scope"], "case");
+    for doc in manifest["documents"].as_array().unwrap() {
+        if doc["bundle_path"] == "witnessing_instructions.pdf" {
+            continue;
+        }
+        let latest: Uuid = sqlx::query_scalar(
+            "SELECT version_id FROM document_versions WHERE document_id = $1::uuid \
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(doc["document_id"].as_str().unwrap())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(doc["version_id"], latest.to_string());
+    }
+    let checksums = std::fs::read_to_string(bundle_path.join("checksums.txt")).unwrap();
+    assert!(checksums.contains(&format!(
+        "{}  witnessing_instructions.pdf",
+        sha256_bytes(&pdf)
+    )));
+    for doc in manifest["documents"].as_array().unwrap() {
+        if let Some(version_id) = doc["version_id"].as_str() {
+            assert!(checksums.contains(&format!(
+                "{}  {}  version_id={version_id}",
+                doc["sha256"].as_str().unwrap(),
+                doc["bundle_path"].as_str().unwrap()
+            )));
+        }
+    }
+    audit_verifier::verify_bundle(&bundle_path).expect("bundle verifies");
+}
+
+#[tokio::test]
+async fn deceased_estate_sa_attach_and_export() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("estate-storage");
+    let export_dir = unique_dir("estate-exports");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+    unsafe {
+        std::env::set_var(
+            "LOCAL_STORAGE_DIR",
+            storage_dir.to_string_lossy().to_string(),
+        );
+        std::env::set_var("LOCAL_EXPORT_DIR", export_dir.to_string_lossy().to_string());
+    }
+
+    let principal_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "deceased_person_id": "00000000-0000-0000-0000-000000000033",
+        "executor_person_id": "00000000-0000-0000-0000-000000000044",
+        "estimated_estate_value_zar": 300000.0,
+        "required_evidence_slots": ["death_certificate"]
+    })
+    .to_string();
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/deceased-estate-sa")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value
+        .get("case_id")
+        .and_then(|v| v.as_str())
+        .unwrap()
+        .to_string();
+
+    let document_id = Uuid::new_v4();
+    let blob_path = storage_dir.join(format!("{}.bin", document_id));
+    std::fs::write(&blob_path, b"death certificate content").unwrap();
+    let sha256 = sha256_bytes(b"death certificate content");
+
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) VALUES ($1, $2, $3::document_type, $4, $5::sensitivity_tier, $6)",
+    )
+    .bind(document_id)
+    .bind(principal_id)
+    .bind("other")
+    .bind("Death Certificate")
+    .bind("red")
+    .bind(Vec::<String>::new())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(&sha256)
+    .bind(25_i64)
+    .bind("application/pdf")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let attach_body = serde_json::json!({"document_id": document_id.to_string()}).to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/death_certificate"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(attach_body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/export"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let bundle_path = export_bundle_dir(&value);
+
+    let manifest_path = bundle_path.join("manifest.json");
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+    assert_eq!(
+        manifest["case_type"].as_str().unwrap(),
+        "deceased_estate_reporting_sa"
+    );
+
+    let instructions_path = bundle_path.join("instructions.md");
+    let instructions = std::fs::read_to_string(&instructions_path).unwrap();
+    assert!(instructions.contains("Letters of Executorship"));
+    assert!(instructions.contains("Letters of Authority"));
+}
+
+#[tokio::test]
+async fn deterministic_export_is_reproducible() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
+    }
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value.get("case_id").and_then(|v| v.as_str()).unwrap();
+
+    let document_id = Uuid::new_v4();
+    let blob_path = storage_dir.join(document_id.to_string());
+    std::fs::write(&blob_path, b"doc").unwrap();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'id', $3, 'amber', ARRAY[]::text[])",
+    )
+    .bind(document_id)
+    .bind(Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap())
+    .bind("ID")
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256_bytes(b"doc"))
+    .bind(3_i64)
+    .bind("text/plain")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let attach_body = serde_json::json!({"document_id": document_id.to_string()}).to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/id"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(attach_body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The archive container must not leak into the bundle: zip and tar.gz exports of
+    // the same case carry identical manifests and checksums, even when another case
+    // records audit events in between.
+    let mut hashes = Vec::new();
+    let mut checksums = Vec::new();
+    let mut head_hashes = Vec::new();
+    for (archive, extension) in [("zip", ".zip"), ("tgz", ".tar.gz")] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!(
+                        "/v1/cases/{case_id}/export?deterministic=true&archive={archive}"
+                    ))
+                    .header("authorization", format!("Bearer {}", token_read()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        hashes.push(value["manifest_sha256"].as_str().unwrap().to_string());
+        let archive_path = value["archive_url"]
+            .as_str()
+            .unwrap()
+            .strip_prefix("file://")
+            .unwrap()
+            .to_string();
+        assert!(archive_path.ends_with(extension));
+        assert_eq!(
+            value["archive_sha256"].as_str().unwrap(),
+            sha256_bytes(&std::fs::read(&archive_path).unwrap())
+        );
+        let bundle_path = export_bundle_dir(&value);
+        checksums.push(std::fs::read_to_string(bundle_path.join("checksums.txt")).unwrap());
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(bundle_path.join("manifest.json")).unwrap())
+                .unwrap();
+        head_hashes.push(manifest["audit_head_hash"].as_str().unwrap().to_string());
+
+        let other_case = serde_json::json!({
+            "subject_person_id": "00000000-0000-0000-0000-000000000033",
+            "applicant_person_id": "00000000-0000-0000-0000-000000000044",
+            "required_evidence_slots": ["id"],
+            "allow_custom_slots": true
+        })
+        .to_string();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/cases/mhca39")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(other_case))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    }
+
+    assert_eq!(hashes[0], hashes[1]);
+    assert_eq!(checksums[0], checksums[1]);
+    assert_eq!(head_hashes[0], head_hashes[1]);
+    let case_head: String = sqlx::query_scalar(
+        "SELECT event_hash FROM audit_events WHERE case_id = $1 \
+         AND action NOT IN ('case.exported', 'export.pruned') ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(Uuid::parse_str(case_id).unwrap())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(head_hashes[0], case_head);
+
+    let kinds: Vec<String> = sqlx::query_scalar(
+        "SELECT kind FROM case_artifacts WHERE case_id = $1 ORDER BY created_at",
+    )
+    .bind(Uuid::parse_str(case_id).unwrap())
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(kinds, vec!["mhca39_export:zip", "mhca39_export:tar.gz"]);
+}
+
+#[tokio::test]
+async fn readiness_score_weighs_death_readiness_documents() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let principal_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let will_id = Uuid::new_v4();
+    let asset_id = Uuid::new_v4();
+    for (document_id, document_type) in [(will_id, "will"), (asset_id, "other")] {
+        sqlx::query(
+            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+             VALUES ($1, $2, $3::document_type, 'Doc', 'amber', ARRAY[]::text[])",
+        )
+        .bind(document_id)
+        .bind(principal_id)
+        .bind(document_type)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "executor_nominee_person_id": "00000000-0000-0000-0000-000000000066",
+        "asset_document_ids": [will_id.to_string(), asset_id.to_string()]
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/death-readiness")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value["case_id"].as_str().unwrap().to_string();
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/cases/{case_id}/score"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    // will (40) + two asset documents (12); the nominee has not accepted a grant yet.
+    assert_eq!(value["score"], 52);
+    assert_eq!(
+        value["missing_recommendations"],
+        serde_json::json!([
+            "have your executor nominee accept their grant",
+            "add asset documents (2 of 3 recommended)",
+            "add contact documents (0 of 2 recommended)"
+        ])
+    );
+
+    let nominee = Uuid::parse_str("00000000-0000-0000-0000-000000000066").unwrap();
+    for (role, status) in [
+        ("executor_nominee", "invited"),
+        ("emergency_contact", "accepted"),
+    ] {
+        sqlx::query(
+            "INSERT INTO role_grants (principal_id, person_id, role, status) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(principal_id)
+        .bind(nominee)
+        .bind(role)
+        .bind(status)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let score_uri = format!("/v1/cases/{case_id}/score");
+    let score = |app: axum::Router| {
+        let uri = score_uri.clone();
+        async move {
+            let response = axum::Router::into_service(app)
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .header("authorization", format!("Bearer {}", token_read()))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()["score"].clone()
+        }
+    };
+    assert_eq!(score(app.clone()).await, 52);
+
+    sqlx::query("UPDATE role_grants SET status = 'accepted' WHERE role = 'executor_nominee'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    // will (40) + accepted executor nominee (20) + two asset documents (12) + no contacts (0)
+    assert_eq!(score(app.clone()).await, 72);
+
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022"
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let mhca39_id = value["case_id"].as_str().unwrap().to_string();
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/cases/{mhca39_id}/score"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn archive_case_hides_case_from_listing() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022"
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value["case_id"].as_str().unwrap().to_string();
+
+    for expected in [StatusCode::OK, StatusCode::CONFLICT] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/v1/cases/{case_id}"))
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+    }
+
+    for (uri, expected_len) in [("/v1/cases", 0), ("/v1/cases?include_archived=true", 1)] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .header("authorization", format!("Bearer {}", token_read()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["items"].as_array().unwrap().len(), expected_len);
+    }
+
+    let events: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM audit_events WHERE case_id = $1 AND action = 'case.archived'",
+    )
+    .bind(Uuid::parse_str(&case_id).unwrap())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+}
+
+#[tokio::test]
+async fn transition_history_lists_transitions_in_order() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'mhca39', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let app = case_service::router();
+    for to_status in ["evidence_collecting", "blocked"] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/cases/{case_id}/transition"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(
+                        serde_json::json!({ "to_status": to_status }).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{case_id}/transitions"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["current_status"], "blocked");
+    let transitions = value["transitions"].as_array().unwrap();
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0]["from_status"], "blocked");
+    assert_eq!(transitions[0]["to_status"], "evidence_collecting");
+    assert_eq!(transitions[1]["to_status"], "blocked");
+}
+
+#[tokio::test]
+async fn reopen_requires_flag_proxy_and_reason() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will_prep_sa', 'exported', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let proxy_token = {
+        let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+        let claims = Claims::new(
+            "00000000-0000-0000-0000-000000000001",
+            Role::Proxy,
+            vec![SensitivityTier::Amber],
+            AccessLevel::LimitedWrite,
+            None,
+            300,
+        );
+        config.issue_token(&claims).expect("token")
+    };
+    let transition = |app: axum::Router, token: String, body: serde_json::Value| {
+        axum::Router::into_service(app).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/transition"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+    let reopen = serde_json::json!({"to_status": "ready", "reason": "replace the witness page"});
+
+    let (closed, open) = {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+        unsafe { std::env::remove_var("ALLOW_REOPEN") };
+        let closed = case_service::router();
+        unsafe { std::env::set_var("ALLOW_REOPEN", "true") };
+        let open = case_service::router();
+        unsafe { std::env::remove_var("ALLOW_REOPEN") };
+        (closed, open)
+    };
+
+    let response = transition(closed, proxy_token.clone(), reopen.clone())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = transition(open.clone(), token_write(), reopen.clone())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = transition(
+        open.clone(),
+        proxy_token.clone(),
+        serde_json::json!({"to_status": "ready", "reason": "  "}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = transition(open, proxy_token, reopen).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let row = sqlx::query(
+        "SELECT c.status::text AS status, \
+                (SELECT t.reason FROM case_transitions t WHERE t.case_id = c.case_id) AS reason, \
+                (SELECT count(*) FROM audit_events e \
+                 WHERE e.case_id = c.case_id AND e.action = 'case.reopened') AS reopened \
+         FROM cases c WHERE c.case_id = $1",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(row.get::<String, _>("status"), "ready");
+    assert_eq!(
+        row.get::<Option<String>, _>("reason").as_deref(),
+        Some("replace the witness page")
+    );
+    assert_eq!(row.get::<i64, _>("reopened"), 1);
+}
+
+#[tokio::test]
+async fn create_popia_incident_rejects_oversized_and_control_text() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let app = case_service::router();
+    let create = |body: serde_json::Value| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/popia-incident")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+    let detail = |response: axum::response::Response| async move {
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        value["detail"].as_str().unwrap().to_string()
+    };
+
+    let response = create(serde_json::json!({
+        "incident_title": "t".repeat(201),
+        "affected_data_classes": ["contact"]
+    }))
+    .await
+    .unwrap();
+    assert_eq!(
+        detail(response).await,
+        "incident_title must be at most 200 characters"
+    );
+
+    let response = create(serde_json::json!({
+        "incident_title": "Laptop stolen",
+        "affected_data_classes": ["contact"],
+        "notes": "reported\u{0000}late"
+    }))
+    .await
+    .unwrap();
+    assert_eq!(
+        detail(response).await,
+        "notes must not contain control characters"
+    );
+
+    let cases: i64 = sqlx::query_scalar("SELECT count(*) FROM cases")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(cases, 0);
+}
+
+#[tokio::test]
+async fn list_revisions_returns_history_in_order() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will_prep_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let app = case_service::router();
+    let list = || {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{case_id}/revisions"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = list().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(payload["revisions"], serde_json::json!([]));
+
+    for notes in ["first draft", "second draft"] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri(format!("/v1/cases/{case_id}"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(
+                        serde_json::json!({ "notes": notes }).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = list().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let revisions = payload["revisions"].as_array().unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0]["revision_number"], 1);
+    assert_eq!(
+        revisions[0]["changes"],
+        serde_json::json!({ "notes": "first draft" })
+    );
+    assert_eq!(revisions[1]["revision_number"], 2);
+    assert_eq!(
+        revisions[1]["actor_principal_id"],
+        "00000000-0000-0000-0000-000000000001"
+    );
+    assert!(revisions[1]["created_at"].is_string());
+}
+
+#[tokio::test]
+async fn update_case_records_revisions_for_non_incident_types() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let will_case: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will_prep_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let estate_case: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'deceased_estate_reporting_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let app = case_service::router();
+    let patch = |case_id: Uuid, body: serde_json::Value| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/v1/cases/{case_id}"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+
+    for notes in ["first draft", "second draft"] {
+        let response = patch(will_case, serde_json::json!({ "notes": notes }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let revisions: Vec<(i32, String)> = sqlx::query_as(
+        "SELECT revision_number, notes FROM will_prep_revisions \
+         WHERE case_id = $1 ORDER BY revision_number",
+    )
+    .bind(will_case)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        revisions,
+        vec![
+            (1, "first draft".to_string()),
+            (2, "second draft".to_string())
+        ]
+    );
+
+    let response = patch(
+        will_case,
+        serde_json::json!({ "notes": "x", "affected_user_count": 10 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(
+        String::from_utf8_lossy(&body)
+            .contains("fields not editable for will_prep_sa cases: affected_user_count")
+    );
+
+    let response = patch(
+        estate_case,
+        serde_json::json!({ "estimated_estate_value_zar": 750000.0 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let value: String = sqlx::query_scalar(
+        "SELECT estimated_estate_value_zar::text FROM deceased_estate_revisions WHERE case_id = $1",
+    )
+    .bind(estate_case)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(value, "750000");
+}
+
+#[tokio::test]
+async fn transition_case_rejects_stale_version() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'mhca39', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    // Hold the row lock so the request reads version 0, then blocks on its UPDATE
+    // while a concurrent writer bumps the version underneath it.
+    let mut writer = pool.begin().await.unwrap();
+    sqlx::query("SELECT 1 FROM cases WHERE case_id = $1 FOR UPDATE")
+        .bind(case_id)
+        .execute(&mut *writer)
+        .await
+        .unwrap();
+
+    let app = case_service::router();
+    let request = tokio::spawn(
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/transition"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({ "to_status": "evidence_collecting" }).to_string(),
+                ))
+                .unwrap(),
+        ),
+    );
+    // Wait until the request is parked on the row lock, i.e. it has read the version.
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(10);
+    loop {
+        let waiting: i64 = sqlx::query_scalar(
+            "SELECT count(*) FROM pg_stat_activity \
+             WHERE datname = current_database() AND wait_event_type = 'Lock' \
+               AND query LIKE 'UPDATE cases%'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        if waiting > 0 {
+            break;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "transition never blocked on the row lock"
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    sqlx::query("UPDATE cases SET version = version + 1 WHERE case_id = $1")
+        .bind(case_id)
+        .execute(&mut *writer)
+        .await
+        .unwrap();
+    writer.commit().await.unwrap();
+
+    let response = request.await.unwrap().unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body).contains("case modified concurrently"));
+
+    let (status, transitions): (String, i64) = sqlx::query_as(
+        "SELECT status::text, (SELECT count(*) FROM case_transitions WHERE case_id = $1) \
+         FROM cases WHERE case_id = $1",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(status, "blocked");
+    assert_eq!(transitions, 0);
+
+    // A fresh request sees the new version and succeeds.
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/transition"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({ "to_status": "evidence_collecting" }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn transition_history_distinguishes_forbidden_from_missing_for_administrators() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'mhca39', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let app = case_service::router();
+    let cases = [
+        (case_id, token_other_principal(), StatusCode::NOT_FOUND),
+        (case_id, token_other_administrator(), StatusCode::FORBIDDEN),
+        (
+            Uuid::new_v4(),
+            token_other_administrator(),
+            StatusCode::NOT_FOUND,
+        ),
+    ];
+    for (target, token, expected) in cases {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/cases/{target}/transitions"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+    }
+}
+
+#[tokio::test]
+async fn attach_evidence_batch_is_atomic() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let owner = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ($1, 'will_prep_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .bind(owner)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    for slot in ["id_document", "asset_list", "beneficiary_details"] {
+        sqlx::query("INSERT INTO case_evidence (case_id, slot_name) VALUES ($1, $2)")
+            .bind(case_id)
+            .bind(slot)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let mine = Uuid::new_v4();
+    let theirs = Uuid::new_v4();
+    for (document_id, principal_id) in [
+        (mine, owner),
+        (
+            theirs,
+            Uuid::parse_str("00000000-0000-0000-0000-000000000999").unwrap(),
+        ),
+    ] {
+        sqlx::query(
+            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+             VALUES ($1, $2, 'id', 'ID', 'amber', ARRAY[]::text[])",
+        )
+        .bind(document_id)
+        .bind(principal_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let version: Uuid = sqlx::query_scalar(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, 'file:///tmp/id', $2, 3, 'text/plain') RETURNING version_id",
+    )
+    .bind(mine)
+    .bind("0".repeat(64))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let app = case_service::router();
+    let send = |slots: serde_json::Value| {
+        let app = app.clone();
+        async move {
+            axum::Router::into_service(app)
+                .oneshot(
+                    Request::builder()
+                        .method("PUT")
+                        .uri(format!("/v1/cases/{case_id}/evidence"))
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {}", token_write()))
+                        .body(Body::from(
+                            serde_json::json!({ "slots": slots }).to_string(),
+                        ))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let response = send(serde_json::json!([
+        { "slot_name": "id_document", "document_id": mine },
+        { "slot_name": "asset_list", "document_id": theirs },
+        { "slot_name": "not_a_slot", "document_id": mine },
+        { "slot_name": "beneficiary_details", "document_id": mine, "version_id": Uuid::new_v4() },
+    ]))
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let detail = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["detail"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(detail.contains("asset_list (document not found)"));
+    assert!(detail.contains("not_a_slot (unknown slot)"));
+    assert!(detail.contains("beneficiary_details (document version not found)"));
+    let attached: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM case_evidence WHERE case_id = $1 AND document_id IS NOT NULL",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(attached, 0);
+
+    let response = send(serde_json::json!([
+        { "slot_name": "id_document", "document_id": mine, "version_id": version },
+        { "slot_name": "asset_list", "document_id": mine },
+    ]))
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["slots"].as_array().unwrap().len(), 2);
+    assert_eq!(value["slots"][0]["version_id"], version.to_string());
+    assert!(value["slots"][1].get("version_id").is_none());
+    assert_eq!(
+        value["missing_slots"],
+        serde_json::json!(["beneficiary_details"])
+    );
+    let pinned: Option<Uuid> = sqlx::query_scalar(
+        "SELECT version_id FROM case_evidence WHERE case_id = $1 AND slot_name = 'id_document'",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(pinned, Some(version));
+}
+
+#[tokio::test]
+async fn evidence_attach_takes_documents_of_the_case_owner() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let owner = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let proxy = Uuid::parse_str("00000000-0000-0000-0000-000000000777").unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ($1, 'will_prep_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .bind(owner)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    for slot in ["id_document", "asset_list"] {
+        sqlx::query("INSERT INTO case_evidence (case_id, slot_name) VALUES ($1, $2)")
+            .bind(case_id)
+            .bind(slot)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    sqlx::query(
+        "INSERT INTO proxy_authorizations (proxy_principal_id, subject_principal_id, scopes) \
+         VALUES ($1, $2, ARRAY['write:limited'])",
+    )
+    .bind(proxy)
+    .bind(owner)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let subjects = Uuid::new_v4();
+    let foreign = Uuid::new_v4();
+    for (document_id, principal_id) in [
+        (subjects, owner),
+        (
+            foreign,
+            Uuid::parse_str("00000000-0000-0000-0000-000000000999").unwrap(),
+        ),
+    ] {
+        sqlx::query(
+            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+             VALUES ($1, $2, 'id', 'ID', 'amber', ARRAY[]::text[])",
+        )
+        .bind(document_id)
+        .bind(principal_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let app = case_service::router();
+    let send = |uri: String, token: String, body: serde_json::Value| {
+        let app = app.clone();
+        async move {
+            axum::Router::into_service(app)
+                .oneshot(
+                    Request::builder()
+                        .method("PUT")
+                        .uri(uri)
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // A proxy attaches the subject's own documents through either path.
+    let response = send(
+        format!("/v1/cases/{case_id}/evidence"),
+        token_proxy(None),
+        serde_json::json!({ "slots": [{ "slot_name": "id_document", "document_id": subjects }] }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(
+        format!("/v1/cases/{case_id}/evidence/asset_list"),
+        token_proxy(None),
+        serde_json::json!({ "document_id": subjects }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Nobody attaches another principal's document, on either path.
+    let response = send(
+        format!("/v1/cases/{case_id}/evidence/asset_list"),
+        token_write(),
+        serde_json::json!({ "document_id": foreign }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = send(
+        format!("/v1/cases/{case_id}/evidence"),
+        token_proxy(None),
+        serde_json::json!({ "slots": [{ "slot_name": "asset_list", "document_id": foreign }] }),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let attached: Vec<Option<Uuid>> = sqlx::query_scalar(
+        "SELECT document_id FROM case_evidence WHERE case_id = $1 ORDER BY slot_name",
+    )
+    .bind(case_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(attached, vec![Some(subjects), Some(subjects)]);
+}
+
+#[tokio::test]
+async fn pinned_evidence_attach_rejects_foreign_and_deleted_documents() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let owner = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ($1, 'will_prep_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .bind(owner)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO case_evidence (case_id, slot_name) VALUES ($1, 'id_document')")
+        .bind(case_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Each document has a real version, so only the document itself can disqualify the pin.
+    let mut pins = Vec::new();
+    for (principal_id, deleted) in [
+        (
+            Uuid::parse_str("00000000-0000-0000-0000-000000000999").unwrap(),
+            false,
+        ),
+        (owner, true),
+    ] {
+        let document_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO documents (principal_id, document_type, title, sensitivity, tags, deleted_at) \
+             VALUES ($1, 'id', 'ID', 'amber', ARRAY[]::text[], CASE WHEN $2 THEN now() END) \
+             RETURNING document_id",
+        )
+        .bind(principal_id)
+        .bind(deleted)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let version_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+             VALUES ($1, 'file:///tmp/id', $2, 3, 'text/plain') RETURNING version_id",
+        )
+        .bind(document_id)
+        .bind("0".repeat(64))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        pins.push((document_id, version_id));
+    }
+
+    let app = case_service::router();
+    for (document_id, version_id) in pins {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/v1/cases/{case_id}/evidence/id_document"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(
+                        serde_json::json!({ "document_id": document_id, "version_id": version_id })
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/v1/cases/{case_id}/evidence"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(
+                        serde_json::json!({ "slots": [{
+                            "slot_name": "id_document",
+                            "document_id": document_id,
+                            "version_id": version_id,
+                        }] })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("id_document (document not found)"));
+    }
+
+    let attached: Option<Uuid> = sqlx::query_scalar(
+        "SELECT document_id FROM case_evidence WHERE case_id = $1 AND slot_name = 'id_document'",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(attached, None);
+}
+
+#[tokio::test]
+async fn link_case_rejects_expiry_beyond_configured_max() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_uuid: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'emergency_pack', 'ready', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO emergency_pack_cases (case_id) VALUES ($1)")
+        .bind(case_uuid)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let app = case_service::router();
+    let link = |hours: i32| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_uuid}/link"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({"expires_in_hours": hours}).to_string(),
+                ))
+                .unwrap(),
+        )
+    };
+
+    let response = link(336).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        value["detail"].as_str().unwrap(),
+        "expires_in_hours must be between 1 and 168"
+    );
+
+    let response = link(2).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(value["share_url"].as_str().unwrap().contains("/v1/share/"));
+}
+
+#[tokio::test]
+async fn share_link_serves_bundle_and_records_access() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_uuid: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'emergency_pack', 'link_issued', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO emergency_pack_cases (case_id, share_link_token, share_link_expires_at) \
+         VALUES ($1, 'share-token', now() + interval '1 hour')",
+    )
+    .bind(case_uuid)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let export_dir = unique_dir("case-share");
+    std::fs::create_dir_all(&export_dir).unwrap();
+    let bundle_path = export_dir.join("emergency-pack.zip");
+    std::fs::write(&bundle_path, b"bundle-bytes").unwrap();
+    sqlx::query(
+        "INSERT INTO case_artifacts (case_id, kind, blob_ref, sha256) VALUES ($1, 'emergency_pack_export', $2, $3)",
+    )
+    .bind(case_uuid)
+    .bind(bundle_path.to_string_lossy().to_string())
+    .bind(sha256_bytes(b"bundle-bytes"))
+    .execute(&pool)
+    .await
+    .unwrap();
+    let app = case_service::router();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/v1/share/share-token")
+                .header("x-forwarded-for", "198.51.100.1, 203.0.113.7")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"bundle-bytes");
+
+    let status: String = sqlx::query_scalar("SELECT status::text FROM cases WHERE case_id = $1")
+        .bind(case_uuid)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "accessed");
+    let accessor_ip: String = sqlx::query_scalar(
+        "SELECT payload->>'accessor_ip' FROM audit_events WHERE case_id = $1 AND action = 'link.accessed'",
+    )
+    .bind(case_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(accessor_ip, "203.0.113.7");
+
+    // A bundle altered on disk is refused and the access is not counted.
+    std::fs::write(&bundle_path, b"tampered-bytes").unwrap();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/v1/share/share-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["code"], "integrity_mismatch");
+    let access_count: i32 = sqlx::query_scalar(
+        "SELECT share_link_access_count FROM emergency_pack_cases WHERE case_id = $1",
+    )
+    .bind(case_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(access_count, 1);
+
+    sqlx::query(
+        "UPDATE emergency_pack_cases SET share_link_expires_at = now() - interval '1 minute' \
+         WHERE case_id = $1",
+    )
+    .bind(case_uuid)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/v1/share/share-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::GONE);
+}
+
+#[tokio::test]
+async fn share_link_expires_after_max_accesses() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_uuid: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'emergency_pack', 'ready', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO emergency_pack_cases (case_id) VALUES ($1)")
+        .bind(case_uuid)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let export_dir = unique_dir("case-share-cap");
+    std::fs::create_dir_all(&export_dir).unwrap();
+    let bundle_path = export_dir.join("emergency-pack.zip");
+    std::fs::write(&bundle_path, b"bundle-bytes").unwrap();
+    sqlx::query(
+        "INSERT INTO case_artifacts (case_id, kind, blob_ref, sha256) VALUES ($1, 'emergency_pack_export', $2, $3)",
+    )
+    .bind(case_uuid)
+    .bind(bundle_path.to_string_lossy().to_string())
+    .bind(sha256_bytes(b"bundle-bytes"))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let app = case_service::router();
+    let link = |max_accesses: i32| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_uuid}/link"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({"max_accesses": max_accesses}).to_string(),
+                ))
+                .unwrap(),
+        )
+    };
+    let response = link(0).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = link(2).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let token = value["share_url"]
+        .as_str()
+        .unwrap()
+        .rsplit('/')
+        .next()
+        .unwrap()
+        .to_string();
+
+    let access = || {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/share/{token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    assert_eq!(access().await.unwrap().status(), StatusCode::OK);
+    assert_eq!(access().await.unwrap().status(), StatusCode::OK);
+    assert_eq!(access().await.unwrap().status(), StatusCode::GONE);
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{case_uuid}"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["status"], "accessed");
+    assert_eq!(value["share_link"]["access_count"], 2);
+    assert_eq!(value["share_link"]["max_accesses"], 2);
+    assert!(value["share_link"]["last_accessed_at"].is_string());
+}
+
+#[tokio::test]
+async fn recipient_bound_share_link_requires_one_time_code() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_uuid: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'emergency_pack', 'ready', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO emergency_pack_cases (case_id) VALUES ($1)")
+        .bind(case_uuid)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let export_dir = unique_dir("case-share-otp");
+    std::fs::create_dir_all(&export_dir).unwrap();
+    let bundle_path = export_dir.join("emergency-pack.zip");
+    std::fs::write(&bundle_path, b"bundle-bytes").unwrap();
+    sqlx::query(
+        "INSERT INTO case_artifacts (case_id, kind, blob_ref, sha256) VALUES ($1, 'emergency_pack_export', $2, $3)",
+    )
+    .bind(case_uuid)
+    .bind(bundle_path.to_string_lossy().to_string())
+    .bind(sha256_bytes(b"bundle-bytes"))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (sender, mut relayed_rx) = tokio::sync::mpsc::unbounded_channel();
+    let relay_app = axum::Router::new().route(
+        "/otp",
+        axum::routing::post(move |body: axum::body::Bytes| {
+            let sender = sender.clone();
+            async move {
+                sender.send(body).unwrap();
+                StatusCode::ACCEPTED
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, relay_app).await.unwrap() });
+    unsafe { std::env::set_var("SHARE_OTP_RELAY_URL", format!("http://{addr}/otp")) };
+
+    let app = case_service::router();
+    let link = |recipient: &str| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_uuid}/link"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({"recipient_email": recipient}).to_string(),
+                ))
+                .unwrap(),
+        )
+    };
+    let response = link("not-an-address").await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = link("Kin@Family.Example").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["recipient_email"], "kin@family.example");
+    let share_url = value["share_url"].as_str().unwrap();
+    let token = share_url.rsplit('/').next().unwrap().to_string();
+
+    let access = |otp: Option<&str>| {
+        let uri = match otp {
+            Some(otp) => format!("/v1/share/{token}?otp={otp}"),
+            None => format!("/v1/share/{token}"),
+        };
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = access(None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let stored: Option<String> = sqlx::query_scalar(
+        "SELECT share_link_otp_sha256 FROM emergency_pack_cases WHERE case_id = $1",
+    )
+    .bind(case_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(stored.is_some());
+
+    // The code only leaves through the relay, addressed to the recipient.
+    let body = tokio::time::timeout(std::time::Duration::from_secs(10), relayed_rx.recv())
+        .await
+        .expect("code relayed")
+        .unwrap();
+    let relayed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(relayed["recipient_email"], "kin@family.example");
+    let code = relayed["code"].as_str().unwrap().to_string();
+
+    // Asking again while that code is live neither replaces nor re-sends it.
+    let response = access(None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let restored: Option<String> = sqlx::query_scalar(
+        "SELECT share_link_otp_sha256 FROM emergency_pack_cases WHERE case_id = $1",
+    )
+    .bind(case_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(restored, stored);
+    assert!(relayed_rx.try_recv().is_err());
+
+    let wrong = if code == "000000" { "111111" } else { "000000" };
+    let response = access(Some(wrong)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = access(Some(&code)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"bundle-bytes");
+
+    // The code is single use.
+    let response = access(Some(&code)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    sqlx::query("UPDATE emergency_pack_cases SET share_link_otp_attempts = 5 WHERE case_id = $1")
+        .bind(case_uuid)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = access(None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let actions: Vec<String> = sqlx::query_scalar(
+        "SELECT action FROM audit_events WHERE case_id = $1 AND action LIKE 'link.otp_%' ORDER BY created_at",
+    )
+    .bind(case_uuid)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(actions, ["link.otp_issued", "link.otp_failed"]);
+}
+
+#[tokio::test]
+async fn link_reaper_expires_past_due_links_on_next_tick() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let case_uuid: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'emergency_pack', 'link_issued', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO emergency_pack_cases (case_id, share_link_token, share_link_expires_at) \
+         VALUES ($1, 'stale-token', now() - interval '1 minute')",
+    )
+    .bind(case_uuid)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let reaper = tokio::spawn(case_service::run_link_reaper(
+        pool.clone(),
+        std::sync::Arc::new(audit_verifier::AuditKeyring::default()),
+        std::time::Duration::from_millis(50),
+    ));
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    reaper.abort();
+
+    let row = sqlx::query(
+        "SELECT c.status::text AS status, e.share_link_token \
+         FROM cases c JOIN emergency_pack_cases e ON e.case_id = c.case_id WHERE c.case_id = $1",
+    )
+    .bind(case_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let status: String = row.try_get("status").unwrap();
+    let token: Option<String> = row.try_get("share_link_token").unwrap();
+    assert_eq!(status, "expired");
+    assert!(token.is_none());
+
+    let events: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM audit_events WHERE case_id = $1 AND action = 'link.expired'",
+    )
+    .bind(case_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+}
+
+#[tokio::test]
+async fn webhook_receives_signed_transition_and_dead_letters_failures() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let receiver_app = axum::Router::new()
+        .route(
+            "/ok",
+            axum::routing::post(
+                move |headers: axum::http::HeaderMap, body: axum::body::Bytes| {
+                    let sender = sender.clone();
+                    async move {
+                        let signature = headers["x-lifeready-signature"]
+                            .to_str()
+                            .unwrap()
+                            .to_string();
+                        sender.send((signature, body)).unwrap();
+                        StatusCode::NO_CONTENT
+                    }
+                },
+            ),
+        )
+        .route(
+            "/down",
+            axum::routing::post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, receiver_app).await.unwrap() });
+
+    let app = case_service::router();
+    let register = |path: &str| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/webhooks")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({
+                        "url": format!("http://{addr}{path}"),
+                        "events": ["case.ready"]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+    };
+    let response = register("/ok").await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let secret = created["secret"].as_str().unwrap().to_string();
+    let stored_secret: Vec<u8> =
+        sqlx::query_scalar("SELECT secret FROM webhooks WHERE webhook_id = $1::uuid")
+            .bind(created["webhook_id"].as_str().unwrap())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(stored_secret.starts_with(b"LRS1"));
+    assert!(
+        !stored_secret
+            .windows(secret.len())
+            .any(|window| window == secret.as_bytes())
+    );
+    let response = register("/down").await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let down_id = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["webhook_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will_prep_sa', 'blocked', ARRAY['evidence incomplete']) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/transition"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(r#"{"to_status":"ready"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (signature, body) =
+        tokio::time::timeout(std::time::Duration::from_secs(10), receiver.recv())
+            .await
+            .expect("webhook delivered")
+            .unwrap();
+    {
+        use hmac::{Hmac, Mac};
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(&body);
+        assert_eq!(
+            signature,
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+        );
+    }
+    let delivered: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(delivered["event"], "case.ready");
+    assert_eq!(delivered["case_id"], case_id.to_string());
+    assert_eq!(delivered["data"]["to_status"], "ready");
+
+    let mut dead_letters = 0i64;
+    for _ in 0..50 {
+        dead_letters = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhook_dead_letters WHERE webhook_id = $1::uuid AND event = 'case.ready'",
+        )
+        .bind(&down_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        if dead_letters > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+    assert_eq!(dead_letters, 1);
+
+    let delete = |token: String| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/v1/webhooks/{down_id}"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let other_writer = AuthConfig::new("test-secret-32-chars-minimum!!")
+        .issue_token(&Claims::new(
+            "00000000-0000-0000-0000-000000000999",
+            Role::Principal,
+            vec![SensitivityTier::Amber],
+            AccessLevel::LimitedWrite,
+            None,
+            300,
+        ))
+        .expect("token");
+    let response = delete(other_writer).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = delete(token_write()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn link_related_case_records_and_lists_links() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let insert_case = |principal: &'static str, case_type: &'static str| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, Uuid>(
+                "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+                 VALUES ($1::uuid, $2::case_type, 'blocked', ARRAY[]::text[]) RETURNING case_id",
+            )
+            .bind(principal)
+            .bind(case_type)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        }
+    };
+    let estate_case = insert_case(
+        "00000000-0000-0000-0000-000000000001",
+        "deceased_estate_reporting_sa",
+    )
+    .await;
+    let will_case = insert_case("00000000-0000-0000-0000-000000000001", "will_prep_sa").await;
+    let foreign_case = insert_case("00000000-0000-0000-0000-000000000999", "will_prep_sa").await;
+
+    let app = case_service::router();
+    let link = |from: Uuid, to: Uuid| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{from}/related"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(
+                    serde_json::json!({
+                        "related_case_id": to,
+                        "relationship": "derived_from"
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+    };
+
+    let response = link(estate_case, will_case).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = link(estate_case, will_case).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = link(estate_case, estate_case).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = link(estate_case, foreign_case).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{will_case}/related"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        payload["links"],
+        serde_json::json!([{
+            "related_case_id": estate_case.to_string(),
+            "relationship": "derived_from",
+            "direction": "incoming",
+            "created_at": payload["links"][0]["created_at"],
+        }])
+    );
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{estate_case}"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        payload["related_cases"],
+        serde_json::json!([{
+            "related_case_id": will_case.to_string(),
+            "relationship": "derived_from",
+            "direction": "outgoing",
+            "created_at": payload["related_cases"][0]["created_at"],
+        }])
+    );
+}
+
+#[tokio::test]
+async fn audit_appends_use_active_key_epoch() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+    sqlx::query("INSERT INTO key_epochs (key_id) VALUES ('k1')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let keys = format!("k1:{}", "11".repeat(32));
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let create = |app: axum::Router| {
+        axum::Router::into_service(app).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body.clone()))
+                .unwrap(),
+        )
+    };
+
+    // The active epoch's key is missing, so the append (and the case) fails closed.
+    unsafe { std::env::remove_var("AUDIT_HMAC_KEYS") };
+    let response = create(case_service::router()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let cases: i64 = sqlx::query_scalar("SELECT count(*) FROM cases")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(cases, 0);
+
+    unsafe { std::env::set_var("AUDIT_HMAC_KEYS", &keys) };
+    let response = create(case_service::router()).await.unwrap();
+    unsafe { std::env::remove_var("AUDIT_HMAC_KEYS") };
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let row = sqlx::query(
+        "SELECT event_id, created_at, actor_principal_id, action, tier::text AS tier, case_id, payload, prev_hash, event_hash, key_id \
+         FROM audit_events",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let key_id: Option<String> = row.try_get("key_id").unwrap();
+    assert_eq!(key_id.as_deref(), Some("k1"));
+    let line = serde_json::json!({
+        "event_id": row.try_get::<Uuid, _>("event_id").unwrap().to_string(),
+        "created_at": row.try_get::<chrono::DateTime<chrono::Utc>, _>("created_at").unwrap().to_rfc3339(),
+        "prev_hash": row.try_get::<String, _>("prev_hash").unwrap(),
+        "event_hash": row.try_get::<String, _>("event_hash").unwrap(),
+        "key_id": key_id,
+        "event": {
+            "actor_principal_id": row.try_get::<Uuid, _>("actor_principal_id").unwrap().to_string(),
+            "action": row.try_get::<String, _>("action").unwrap(),
+            "tier": row.try_get::<String, _>("tier").unwrap(),
+            "case_id": row.try_get::<Option<Uuid>, _>("case_id").unwrap().map(|id| id.to_string()),
+            "payload": row.try_get::<serde_json::Value, _>("payload").unwrap(),
+        },
+    });
+    let dir = unique_dir("case-audit-keyed");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("audit.jsonl");
+    std::fs::write(&path, line.to_string()).unwrap();
+
+    let keyring = audit_verifier::AuditKeyring::parse(&keys).unwrap();
+    audit_verifier::verify_audit_chain_with_keys(&path, None, &keyring).expect("keyed chain");
+    assert!(audit_verifier::verify_audit_chain(&path, None).is_err());
+}
+
+#[tokio::test]
+async fn create_validate_only_writes_nothing() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "principal_person_id": "00000000-0000-0000-0000-000000000011",
+        "attorney_person_id": "00000000-0000-0000-0000-000000000022",
+        "powers_scope": "general"
+    })
+    .to_string();
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/power-of-attorney-sa?validate=true")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .header("idempotency-key", "dry-run-1")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("location").is_none());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value, serde_json::json!({"valid": true}));
+
+    let writes: i64 = sqlx::query_scalar(
+        "SELECT (SELECT count(*) FROM cases) + (SELECT count(*) FROM audit_events) \
+         + (SELECT count(*) FROM idempotency_keys)",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(writes, 0);
+}
+
+#[tokio::test]
+async fn export_audit_excludes_other_principals_events() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
+    }
+
+    let app = case_service::router();
+    let create_body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let mut case_ids = Vec::new();
+    // The other principal's case and a second case of our own land between ours and
+    // the evidence attach, so the bundled trail has to skip over both.
+    for token in [token_write(), token_other_principal_write(), token_write()] {
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/cases/mhca39")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::from(create_body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        case_ids.push(value["case_id"].as_str().unwrap().to_string());
+    }
+    let case_id = &case_ids[0];
+    let other_case_id = &case_ids[1];
+    let own_other_case_id = &case_ids[2];
+
+    let document_id = Uuid::new_v4();
+    let blob_path = storage_dir.join(document_id.to_string());
+    std::fs::write(&blob_path, b"doc").unwrap();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'id', $3, 'amber', ARRAY[]::text[])",
+    )
+    .bind(document_id)
+    .bind(Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap())
+    .bind("ID")
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256_bytes(b"doc"))
+    .bind(3_i64)
+    .bind("text/plain")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let attach_body = serde_json::json!({"document_id": document_id.to_string()}).to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/id"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(attach_body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/export"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let bundle_path = export_bundle_dir(&value);
+
+    let audit_contents = std::fs::read_to_string(bundle_path.join("audit.jsonl")).unwrap();
+    let events: Vec<serde_json::Value> = audit_contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(!events.is_empty());
+    for event in &events {
+        assert_eq!(
+            event["event"]["actor_principal_id"],
+            "00000000-0000-0000-0000-000000000001"
+        );
+        assert_eq!(event["event"]["case_id"], case_id.as_str());
+    }
+    for excluded in [other_case_id, own_other_case_id] {
+        let excluded_events: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM audit_events WHERE case_id = $1::uuid")
+                .bind(excluded)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(excluded_events > 0);
+    }
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(bundle_path.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(
+        manifest["audit_head_hash"],
+        events.last().unwrap()["event_hash"]
+    );
+    audit_verifier::verify_bundle(&bundle_path).expect("bundle verifies");
+}
+
+/// Creates an MHCA39 case for principal ...001 and, when `complete`, attaches a
+/// versioned document to its only required slot.
+async fn create_mhca39_case(
+    app: &axum::Router,
+    pool: &PgPool,
+    storage_dir: &std::path::Path,
+    complete: bool,
+) -> String {
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": ["id"],
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value["case_id"].as_str().unwrap().to_string();
+    if !complete {
+        return case_id;
+    }
+
+    let document_id = Uuid::new_v4();
+    let blob_path = storage_dir.join(document_id.to_string());
+    std::fs::write(&blob_path, b"doc").unwrap();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'id', 'ID', 'amber', ARRAY[]::text[])",
+    )
+    .bind(document_id)
+    .bind(Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap())
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 3, 'text/plain')",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(hex::encode(sha2::Sha256::digest(b"doc")))
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let attach_body = serde_json::json!({"document_id": document_id.to_string()}).to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/cases/{case_id}/evidence/id"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(attach_body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    case_id
+}
+
+#[tokio::test]
+async fn export_bundle_packages_each_case_and_rejects_incomplete() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
+    }
+
+    let app = case_service::router();
+    let first = create_mhca39_case(&app, &pool, &storage_dir, true).await;
+    let second = create_mhca39_case(&app, &pool, &storage_dir, true).await;
+    let incomplete = create_mhca39_case(&app, &pool, &storage_dir, false).await;
+    let export_bundle = |case_ids: Vec<&str>| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/export-bundle")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_read_packs()))
+                .body(Body::from(
+                    serde_json::json!({ "case_ids": case_ids }).to_string(),
+                ))
+                .unwrap(),
+        )
+    };
+
+    let response = export_bundle(vec![&first, &incomplete]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let detail = problem["detail"].as_str().unwrap();
+    assert!(detail.contains(&incomplete), "{detail}");
+    assert!(!detail.contains(&first), "{detail}");
+    assert!(!export_dir.join("bundles").exists());
+
+    let response = export_bundle(vec![&first, &second]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let bundle_id = value["bundle_id"].as_str().unwrap();
+    let bundle_dir = export_dir.join("bundles").join(bundle_id);
+
+    let manifest_bytes = std::fs::read(bundle_dir.join("manifest.json")).unwrap();
+    assert_eq!(
+        value["manifest_sha256"],
+        hex::encode(sha2::Sha256::digest(&manifest_bytes))
+    );
+    let manifest: serde_json::Value = serde_json::from_slice(&manifest_bytes).unwrap();
+    let cases = manifest["cases"].as_array().unwrap();
+    assert_eq!(cases.len(), 2);
+    let checksums = std::fs::read_to_string(bundle_dir.join("checksums.txt")).unwrap();
+    for (entry, case_id) in cases.iter().zip([&first, &second]) {
+        assert_eq!(entry["case_id"], case_id.as_str());
+        assert_eq!(entry["case_type"], "mhca39");
+        let case_manifest = std::fs::read(bundle_dir.join(case_id).join("manifest.json")).unwrap();
+        assert_eq!(
+            entry["manifest_sha256"],
+            hex::encode(sha2::Sha256::digest(&case_manifest))
+        );
+        assert!(checksums.contains(&format!("  {case_id}/manifest.json")));
+        audit_verifier::verify_bundle(&bundle_dir.join(case_id)).expect("case verifies");
+    }
+
+    let payload: serde_json::Value = sqlx::query_scalar(
+        "SELECT payload FROM audit_events \
+         WHERE action = 'cases.bundle_exported' AND payload->>'bundle_id' = $1",
+    )
+    .bind(bundle_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(payload["case_ids"], serde_json::json!([first, second]));
+
+    // The bundle is recorded against each case it holds and served over the API.
+    let recorded: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT case_id, kind FROM case_artifacts WHERE bundle_id = $1::uuid ORDER BY created_at",
+    )
+    .bind(bundle_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(recorded.len(), 2);
+    assert!(recorded.iter().all(|(_, kind)| kind == "bundle:zip"));
+    let download_url = value["download_url"].as_str().unwrap();
+    let download_path = &download_url[download_url.find("/v1/").unwrap()..];
+    assert_eq!(
+        download_path,
+        format!("/v1/cases/export-bundle/{bundle_id}")
+    );
+    let download = |token: String| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(download_path)
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let response = download(token_read_packs()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/zip");
+    let bundle = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        hex::encode(sha2::Sha256::digest(&bundle)),
+        value["archive_sha256"].as_str().unwrap()
+    );
+    let response = download(token_other_principal()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn bundled_emergency_pack_share_link_serves_only_its_own_export() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
+    }
+
+    let app = case_service::router();
+    let other = create_mhca39_case(&app, &pool, &storage_dir, true).await;
+
+    let directive = Uuid::new_v4();
+    let blob_path = storage_dir.join(directive.to_string());
+    std::fs::write(&blob_path, b"directive").unwrap();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, '00000000-0000-0000-0000-000000000001', 'will', 'Directive', 'amber', ARRAY[]::text[])",
+    )
+    .bind(directive)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 9, 'text/plain')",
+    )
+    .bind(directive)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256_bytes(b"directive"))
+    .execute(&pool)
+    .await
+    .unwrap();
+    let pack: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'emergency_pack', 'link_issued', ARRAY[]::text[]) \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO emergency_pack_cases (case_id, directive_document_ids, share_link_token, share_link_expires_at) \
+         VALUES ($1, ARRAY[$2]::uuid[], 'bundled-pack-token', now() + interval '1 hour')",
+    )
+    .bind(pack)
+    .bind(directive)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let pack_export = export_dir.join("emergency-pack.zip");
+    std::fs::write(&pack_export, b"pack-only").unwrap();
+    let pack_artifact: Uuid = sqlx::query_scalar(
+        "INSERT INTO case_artifacts (case_id, kind, blob_ref, sha256) \
+         VALUES ($1, 'emergency_pack_export:zip', $2, $3) RETURNING artifact_id",
+    )
+    .bind(pack)
+    .bind(pack_export.to_string_lossy().to_string())
+    .bind(sha256_bytes(b"pack-only"))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/export-bundle")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_read_packs()))
+                .body(Body::from(
+                    serde_json::json!({ "case_ids": [pack.to_string(), other] }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The bundle row is newer, but the share link must keep serving the pack's own export.
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/v1/share/bundled-pack-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"pack-only");
+
+    // Nor can the single-case download reach the bundle through the pack.
+    let bundle_artifact: Uuid = sqlx::query_scalar(
+        "SELECT artifact_id FROM case_artifacts WHERE case_id = $1 AND bundle_id IS NOT NULL",
+    )
+    .bind(pack)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let download = |artifact: Uuid| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/cases/{pack}/export/{artifact}"))
+                .header("authorization", format!("Bearer {}", token_read_packs()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let response = download(bundle_artifact).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = download(pack_artifact).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn create_mhca39_inserts_every_custom_slot_in_one_batch() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let slots = ["id", "medical_certificate_1", "custom_affidavit", "zz_last"];
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": slots,
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let response = axum::Router::into_service(case_service::router())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = Uuid::parse_str(value["case_id"].as_str().unwrap()).unwrap();
+
+    let rows: Vec<(String, Option<Uuid>)> = sqlx::query_as(
+        "SELECT slot_name, document_id FROM mhca39_evidence WHERE case_id = $1 ORDER BY slot_name",
+    )
+    .bind(case_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    let mut expected: Vec<(String, Option<Uuid>)> =
+        slots.iter().map(|slot| (slot.to_string(), None)).collect();
+    expected.sort();
+    assert_eq!(rows, expected);
+}
+
+#[tokio::test]
+async fn create_mhca39_rejects_too_many_slots() {
+    init_env();
+    if setup_db().await.is_none() {
+        return;
+    }
+
+    let slots: Vec<String> = (0..51).map(|index| format!("slot_{index}")).collect();
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": slots,
+        "allow_custom_slots": true
+    })
+    .to_string();
+    let response = axum::Router::into_service(case_service::router())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        problem["detail"],
+        "required_evidence_slots exceeds the limit of 50"
+    );
+}
+
+#[tokio::test]
+async fn create_inserts_default_slot_set_in_one_statement() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    let cases = [
+        (
+            "/v1/cases/mhca39",
+            serde_json::json!({
+                "subject_person_id": "00000000-0000-0000-0000-000000000011",
+                "applicant_person_id": "00000000-0000-0000-0000-000000000022"
+            }),
+            "SELECT required_evidence_slots FROM mhca39_cases WHERE case_id = $1",
+            "SELECT slot_name FROM mhca39_evidence WHERE case_id = $1",
+        ),
+        (
+            "/v1/cases/will-prep-sa",
+            serde_json::json!({
+                "principal_person_id": "00000000-0000-0000-0000-000000000011"
+            }),
+            "SELECT required_evidence_slots FROM will_prep_cases WHERE case_id = $1",
+            "SELECT slot_name FROM case_evidence WHERE case_id = $1",
+        ),
+        (
+            "/v1/cases/deceased-estate-sa",
+            serde_json::json!({
+                "deceased_person_id": "00000000-0000-0000-0000-000000000011",
+                "executor_person_id": "00000000-0000-0000-0000-000000000022"
+            }),
+            "SELECT required_evidence_slots FROM deceased_estate_cases WHERE case_id = $1",
+            "SELECT slot_name FROM case_evidence WHERE case_id = $1",
+        ),
+        (
+            "/v1/cases/popia-incident",
+            serde_json::json!({
+                "incident_title": "Laptop stolen",
+                "affected_data_classes": ["contact"]
+            }),
+            "SELECT required_evidence_slots FROM popia_incident_cases WHERE case_id = $1",
+            "SELECT slot_name FROM case_evidence WHERE case_id = $1",
+        ),
+    ];
+    for (uri, body, required_query, evidence_query) in cases {
+        let response = axum::Router::into_service(case_service::router())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED, "{uri}");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let case_id = Uuid::parse_str(value["case_id"].as_str().unwrap()).unwrap();
+
+        let mut required: Vec<String> = sqlx::query_scalar(required_query)
+            .bind(case_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let mut evidence: Vec<String> = sqlx::query_scalar(evidence_query)
+            .bind(case_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert!(!required.is_empty(), "{uri}");
+        required.sort();
+        evidence.sort();
+        assert_eq!(evidence, required, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn inline_export_returns_structured_payload_within_cap() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    let storage_dir = unique_dir("inline-storage");
+    let export_dir = unique_dir("inline-exports");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+    unsafe {
+        std::env::set_var(
+            "LOCAL_STORAGE_DIR",
+            storage_dir.to_string_lossy().to_string(),
+        );
+        std::env::set_var("LOCAL_EXPORT_DIR", export_dir.to_string_lossy().to_string());
+        std::env::remove_var("EXPORT_INLINE_MAX_BYTES");
+    }
+
+    let principal_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ($1, 'will_prep_sa', 'ready', ARRAY[]::text[]) RETURNING case_id",
+    )
+    .bind(principal_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO will_prep_cases (case_id, principal_person_id, required_evidence_slots) \
+         VALUES ($1, $2, ARRAY['draft_will_document'])",
+    )
+    .bind(case_id)
+    .bind(principal_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let document_id = Uuid::new_v4();
+    let blob_path = storage_dir.join(format!("{}.bin", document_id));
+    std::fs::write(&blob_path, b"draft will content").unwrap();
+    sqlx::query(
+        "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+         VALUES ($1, $2, 'will', 'Draft Will', 'amber', ARRAY[]::text[])",
+    )
+    .bind(document_id)
+    .bind(principal_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 18, 'application/pdf')",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256_bytes(b"draft will content"))
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO case_evidence (case_id, slot_name, document_id) \
+         VALUES ($1, 'draft_will_document', $2)",
+    )
+    .bind(case_id)
+    .bind(document_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let export = |app: axum::Router| async move {
+        let response = axum::Router::into_service(app)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/cases/{case_id}/export?inline=true"))
+                    .header("authorization", format!("Bearer {}", token_read()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+    };
+
+    let value = export(case_service::router()).await;
+    let inline = &value["inline"];
+    assert_eq!(inline["manifest"]["case_id"], case_id.to_string());
+    assert_eq!(inline["template"]["case_id"], case_id.to_string());
+    let bundle_path = export_bundle_dir(&value);
+    let manifest_bytes = std::fs::read(bundle_path.join("manifest.json")).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&manifest_bytes).unwrap(),
+        inline["manifest"]
+    );
+    let documents = inline["documents"].as_array().unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0]["document_id"], document_id.to_string());
+    assert_eq!(
+        documents[0]["sha256"],
+        sha256_bytes(b"draft will content").as_str()
+    );
+    use base64::Engine;
+    let content = base64::engine::general_purpose::STANDARD
+        .decode(documents[0]["content_base64"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(content, b"draft will content");
+
+    unsafe {
+        std::env::set_var("EXPORT_INLINE_MAX_BYTES", "16");
+    }
+    let capped = case_service::router();
+    unsafe {
+        std::env::remove_var("EXPORT_INLINE_MAX_BYTES");
+    }
+    let value = export(capped).await;
+    assert!(value.get("inline").is_none());
+    assert!(
+        value["archive_url"]
+            .as_str()
+            .unwrap()
+            .starts_with("file://")
+    );
+}
+
+#[tokio::test]
+async fn case_grant_lets_executor_nominee_read_but_not_write() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+    let app = case_service::router();
+    let principal_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let nominee_id = "00000000-0000-0000-0000-000000000999";
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status, blocked_reasons) \
+         VALUES ($1, 'death_readiness', 'draft', ARRAY[]::text[]) RETURNING case_id",
+    )
+    .bind(principal_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO death_readiness_cases (case_id, executor_nominee_person_id) VALUES ($1, $2)",
+    )
+    .bind(case_id)
+    .bind(Uuid::parse_str(nominee_id).unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let send = |method: &str, uri: String, token: String, body: Option<serde_json::Value>| {
+        let app = app.clone();
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {token}"))
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+        async move { app.oneshot(request).await.unwrap() }
+    };
+    let transitions = format!("/v1/cases/{case_id}/transitions");
+    let grants = format!("/v1/cases/{case_id}/grants");
+    let grant = serde_json::json!({"grantee_principal_id": nominee_id, "role": "executor_nominee"});
+
+    let response = send(
+        "GET",
+        transitions.clone(),
+        token_other_executor_nominee(),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Only the owner can grant, and not to themselves.
+    let response = send(
+        "POST",
+        grants.clone(),
+        token_other_principal_write(),
+        Some(serde_json::json!({
+            "grantee_principal_id": "00000000-0000-0000-0000-000000000002",
+            "role": "executor_nominee",
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = send(
+        "POST",
+        grants.clone(),
+        token_write(),
+        Some(serde_json::json!({
+            "grantee_principal_id": principal_id.to_string(),
+            "role": "executor_nominee",
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = send("POST", grants.clone(), token_write(), Some(grant.clone())).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created["grantee_principal_id"], nominee_id);
+    assert_eq!(created["role"], "executor_nominee");
+    let response = send("POST", grants.clone(), token_write(), Some(grant)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let repeated: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(repeated["granted_at"], created["granted_at"]);
+
+    let response = send(
+        "GET",
+        transitions.clone(),
+        token_other_executor_nominee(),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(
+        "GET",
+        format!("/v1/cases/{case_id}/score"),
+        token_other_executor_nominee(),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The grant is tied to the nominee role: the same principal acting as a
+    // principal still cannot see or change the case.
+    let response = send("GET", transitions, token_other_principal(), None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = send(
+        "POST",
+        format!("/v1/cases/{case_id}/transition"),
+        token_other_principal_write(),
+        Some(serde_json::json!({"to_status": "ready"})),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let granted: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM audit_events WHERE case_id = $1 AND action = 'case.access_granted'",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(granted, 1);
+}
+
+#[tokio::test]
+async fn proxy_acts_for_subject_only_within_authorized_scopes() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+    let app = case_service::router();
+    let subject_id = "00000000-0000-0000-0000-000000000001";
+    let proxy_id = Uuid::parse_str("00000000-0000-0000-0000-000000000777").unwrap();
+
+    let send = |method: &str, uri: String, token: String, body: Option<serde_json::Value>| {
+        let app = app.clone();
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {token}"))
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+        async move { app.oneshot(request).await.unwrap() }
+    };
+    let pack = serde_json::json!({
+        "directive_document_ids": [],
+        "emergency_contacts": [{"name": "Sam Doe", "phone_e164": "+27821234567"}],
+    });
+
+    let response = send(
+        "POST",
+        "/v1/cases/emergency-pack".into(),
+        token_proxy(Some(subject_id)),
+        Some(pack.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    sqlx::query(
+        "INSERT INTO proxy_authorizations (proxy_principal_id, subject_principal_id, scopes) \
+         VALUES ($1, $2, ARRAY['write:limited'])",
+    )
+    .bind(proxy_id)
+    .bind(Uuid::parse_str(subject_id).unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let response = send(
+        "POST",
+        "/v1/cases/emergency-pack".into(),
+        token_proxy(Some(subject_id)),
+        Some(pack),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = Uuid::parse_str(value["case_id"].as_str().unwrap()).unwrap();
+    let owner: Uuid = sqlx::query_scalar("SELECT principal_id FROM cases WHERE case_id = $1")
+        .bind(case_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(owner.to_string(), subject_id);
+
+    // The authorization covers writes only, so reads through the proxy stay hidden.
+    let response = send(
+        "GET",
+        format!("/v1/cases/{case_id}/transitions"),
+        token_proxy(None),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = send(
+        "POST",
+        format!("/v1/cases/{case_id}/transition"),
+        token_proxy(None),
+        Some(serde_json::json!({"to_status": "ready"})),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let actor: Uuid = sqlx::query_scalar(
+        "SELECT actor_principal_id FROM case_transitions WHERE case_id = $1 AND to_status = 'ready'",
+    )
+    .bind(case_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(actor, proxy_id);
+
+    sqlx::query("DELETE FROM proxy_authorizations WHERE proxy_principal_id = $1")
+        .bind(proxy_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = send(
+        "POST",
+        format!("/v1/cases/{case_id}/transition"),
+        token_proxy(None),
+        Some(serde_json::json!({"to_status": "link_issued"})),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn list_cases_pages_with_cursor_and_total() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    // Two cases share a timestamp so the cursor has to break the tie on id; the other
+    // principal's case must not count towards the total.
+    for (principal_id, created_at) in [
+        (
+            "00000000-0000-0000-0000-000000000001",
+            "2025-01-01T00:00:00Z",
+        ),
+        (
+            "00000000-0000-0000-0000-000000000001",
+            "2025-02-01T00:00:00Z",
+        ),
+        (
+            "00000000-0000-0000-0000-000000000001",
+            "2025-02-01T00:00:00Z",
+        ),
+        (
+            "00000000-0000-0000-0000-000000000001",
+            "2025-03-01T00:00:00Z",
+        ),
+        (
+            "00000000-0000-0000-0000-000000000001",
+            "2025-04-01T00:00:00Z",
+        ),
+        (
+            "00000000-0000-0000-0000-000000000002",
+            "2025-05-01T00:00:00Z",
+        ),
+    ] {
+        sqlx::query(
+            "INSERT INTO cases (principal_id, case_type, status, blocked_reasons, created_at) \
+             VALUES ($1::uuid, 'emergency_pack', 'draft', ARRAY[]::text[], $2::timestamptz)",
+        )
+        .bind(principal_id)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let app = case_service::router();
+    let list = |uri: String| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let mut seen = Vec::new();
+    let mut uri = "/v1/cases?limit=2&with_total=true".to_string();
+    loop {
+        let response = list(uri).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["page"]["total"], 5);
+        assert_eq!(value["page"]["limit"], 2);
+        let items = value["items"].as_array().unwrap();
+        assert_eq!(value["page"]["returned"], items.len());
+        seen.extend(items.iter().map(|item| item["case_id"].clone()));
+        match value["page"]["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("/v1/cases?limit=2&with_total=true&cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(seen.len(), 5);
+    seen.sort_by_key(|id| id.to_string());
+    seen.dedup();
+    assert_eq!(seen.len(), 5);
+
+    let response = list("/v1/cases".to_string()).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(value["page"].get("total").is_none());
+    assert!(value["page"].get("next_cursor").is_none());
+    assert_eq!(value["page"]["returned"], 5);
+
+    let response = list("/v1/cases?cursor=garbage".to_string()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn list_cases_filters_by_created_window() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let app = case_service::router();
+    let mut case_ids = Vec::new();
+    for created_at in [
+        "2025-01-01T00:00:00Z",
+        "2025-02-01T00:00:00Z",
+        "2025-03-01T00:00:00Z",
+    ] {
+        let body = serde_json::json!({
+            "subject_person_id": "00000000-0000-0000-0000-000000000011",
+            "applicant_person_id": "00000000-0000-0000-0000-000000000022"
+        })
+        .to_string();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/cases/mhca39")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let case_id = Uuid::parse_str(value["case_id"].as_str().unwrap()).unwrap();
+        sqlx::query("UPDATE cases SET created_at = $2::timestamptz WHERE case_id = $1")
+            .bind(case_id)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        case_ids.push(case_id.to_string());
+    }
+
+    let list = |uri: &'static str| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    // `created_before` is exclusive, so the March case falls outside the window.
+    let response =
+        list("/v1/cases?created_after=2025-01-15T00:00:00Z&created_before=2025-03-01T00:00:00Z")
+            .await
+            .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let items = value["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["case_id"], case_ids[1].as_str());
+
+    let response = list("/v1/cases?created_after=2025-01-01T00:00:00Z&limit=1")
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let items = value["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["case_id"], case_ids[2].as_str());
+
+    let response =
+        list("/v1/cases?created_after=2025-03-01T00:00:00Z&created_before=2025-01-01T00:00:00Z")
+            .await
+            .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn export_death_readiness_without_documents_is_rejected() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("case-storage");
+    let export_dir = unique_dir("case-export");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    unsafe {
+        std::env::set_var("LOCAL_STORAGE_DIR", &storage_dir);
+        std::env::set_var("LOCAL_EXPORT_DIR", &export_dir);
+    }
+
+    let app = case_service::router();
+    // One case with nothing attached, one whose only document no longer resolves.
+    for asset_document_ids in [vec![], vec![Uuid::new_v4().to_string()]] {
+        let body = serde_json::json!({
+            "executor_nominee_person_id": "00000000-0000-0000-0000-000000000066",
+            "asset_document_ids": asset_document_ids
+        })
+        .to_string();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/cases/death-readiness")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let case_id = value["case_id"].as_str().unwrap().to_string();
+
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/cases/{case_id}/export"))
+                    .header("authorization", format!("Bearer {}", token_read()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["code"], "evidence_incomplete");
+        assert_eq!(problem["detail"], "no documents attached");
+    }
+    assert_eq!(std::fs::read_dir(&export_dir).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn validate_evidence_reports_each_slot() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let app = case_service::router();
+    let body = serde_json::json!({
+        "subject_person_id": "00000000-0000-0000-0000-000000000011",
+        "applicant_person_id": "00000000-0000-0000-0000-000000000022",
+        "required_evidence_slots": [
+            "applicant_id_copy",
+            "patient_id_copy",
+            "medical_certificate_1",
+            "medical_certificate_2"
+        ]
+    })
+    .to_string();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/cases/mhca39")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let case_id = value["case_id"].as_str().unwrap().to_string();
+
+    let principal_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    for (slot, document_type, mime_type) in [
+        ("applicant_id_copy", "id", "application/pdf"),
+        ("patient_id_copy", "id", "text/csv"),
+        ("medical_certificate_1", "statement", "application/pdf"),
+    ] {
+        let document_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+             VALUES ($1, $2, $3::document_type, 'Doc', 'amber', ARRAY[]::text[])",
+        )
+        .bind(document_id)
+        .bind(principal_id)
+        .bind(document_type)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+             VALUES ($1, 'blob', $2, 1, $3)",
+        )
+        .bind(document_id)
+        .bind("a".repeat(64))
+        .bind(mime_type)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let attach = serde_json::json!({"document_id": document_id.to_string()}).to_string();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/v1/cases/{case_id}/evidence/{slot}"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(attach))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/validate-evidence"))
+                .header(
+                    "authorization",
+                    format!("Bearer {}", token_other_principal_write()),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/cases/{case_id}/validate-evidence"))
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report["ok"], false);
+    assert_eq!(
+        report["items"],
+        serde_json::json!([
+            {"slot": "applicant_id_copy", "ok": true},
+            {
+                "slot": "medical_certificate_1",
+                "ok": false,
+                "reason": "document_type statement not accepted; expected medical_letter"
+            },
+            {"slot": "medical_certificate_2", "ok": false, "reason": "no document attached"},
+            {
+                "slot": "patient_id_copy",
+                "ok": false,
+                "reason": "mime type text/csv not accepted; expected application/pdf, image/jpeg, image/png, image/tiff"
+            }
+        ])
+    );
 }
@@ -177,11 +177,14 @@ async fn export_bundle_verifies_and_detects_tamper() {
     .await;
     assert_eq!(export_res.status(), StatusCode::OK);
     let export_value = body_json(export_res).await;
-    let download_url = export_value
-        .get("download_url")
+    let archive_url = export_value
+        .get("archive_url")
         .and_then(|v| v.as_str())
-        .expect("download_url");
-    let bundle_path = download_url.strip_prefix("file://").expect("file url");
+        .expect("archive_url");
+    let bundle_path = archive_url
+        .strip_prefix("file://")
+        .and_then(|path| path.strip_suffix(".zip"))
+        .expect("file url to a zip archive");
 
     verify_bundle(std::path::Path::new(bundle_path)).expect("bundle valid");
 
@@ -0,0 +1,7 @@
+-- people.principal_id owns the contact record; linked_principal_id is the account the
+-- person signs in with, once they have one. Vault handovers resolve the deceased and
+-- the executor of a deceased estate case through it.
+ALTER TABLE people
+  ADD COLUMN IF NOT EXISTS linked_principal_id uuid REFERENCES principals(principal_id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_people_linked_principal ON people(linked_principal_id);
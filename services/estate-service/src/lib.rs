@@ -1,12 +1,16 @@
 use axum::{
     Json, Router,
     extract::{Extension, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::IntoResponse,
     routing::{get, post},
 };
 use chrono::Utc;
 use lifeready_audit::{AuditEvent, InMemoryAuditSink};
-use lifeready_auth::{AuthConfig, AuthLayer, RequestContext, RequestId, request_id_middleware};
+use lifeready_auth::{
+    AuthConfig, AuthLayer, JsonBody, METRICS_CONTENT_TYPE, METRICS_PATH, RequestContext, RequestId,
+    RevokedTokens, request_id_middleware,
+};
 use lifeready_policy::{
     Role, SensitivityTier, TierRequirement, require_role, require_scope, require_tier,
 };
@@ -27,16 +31,19 @@ pub fn router() -> Router {
         AuthConfig::from_env_checked()
             .expect("AuthConfig misconfigured (check LIFEREADY_ENV and JWT_SECRET)"),
     );
+    // Estate keeps no tables of its own yet, but revocations are shared across services.
+    let revoked = RevokedTokens::from_pool(pool_from_env().as_ref());
 
     Router::new()
         .route("/healthz", get(healthz))
         .route("/readyz", get(readyz))
+        .route(METRICS_PATH, get(metrics_text))
         .route("/v1/people", post(create_person).get(list_people))
         .route("/v1/assets", post(create_asset))
         .route("/v1/instructions", post(create_instruction))
         .route("/v1/roles/grants", post(create_role_grant))
         .with_state(state)
-        .layer(AuthLayer::new(auth_config))
+        .layer(AuthLayer::new(auth_config).with_revocations(revoked))
         .layer(axum::middleware::from_fn(request_id_middleware))
 }
 
@@ -44,6 +51,14 @@ async fn healthz() -> &'static str {
     "ok"
 }
 
+/// Policy denial counters from `lifeready_policy::metrics`, in Prometheus text format.
+async fn metrics_text() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)],
+        lifeready_policy::metrics::render(),
+    )
+}
+
 async fn readyz() -> (StatusCode, Json<serde_json::Value>) {
     let db_ready = check_db().await.is_some();
     if db_ready {
@@ -149,7 +164,7 @@ async fn create_person(
     State(state): State<AppState>,
     ctx: RequestContext,
     Extension(request_id): Extension<RequestId>,
-    Json(payload): Json<PersonCreate>,
+    JsonBody(payload): JsonBody<PersonCreate>,
 ) -> Result<(StatusCode, Json<PersonResponse>), axum::response::Response> {
     require_role(&ctx, &[Role::Principal, Role::Proxy])
         .map_err(|error| error.into_response(Some(request_id)))?;
@@ -197,7 +212,7 @@ async fn create_asset(
     State(state): State<AppState>,
     ctx: RequestContext,
     Extension(request_id): Extension<RequestId>,
-    Json(payload): Json<AssetCreate>,
+    JsonBody(payload): JsonBody<AssetCreate>,
 ) -> Result<(StatusCode, Json<AssetResponse>), axum::response::Response> {
     let tier = payload.sensitivity.unwrap_or(SensitivityTier::Amber);
     require_role(&ctx, &[Role::Principal, Role::Proxy])
@@ -231,7 +246,7 @@ async fn create_instruction(
     State(state): State<AppState>,
     ctx: RequestContext,
     Extension(request_id): Extension<RequestId>,
-    Json(payload): Json<InstructionCreate>,
+    JsonBody(payload): JsonBody<InstructionCreate>,
 ) -> Result<(StatusCode, Json<InstructionResponse>), axum::response::Response> {
     let tier = payload.sensitivity.unwrap_or(SensitivityTier::Amber);
     require_role(&ctx, &[Role::Principal, Role::Proxy])
@@ -264,7 +279,7 @@ async fn create_role_grant(
     State(state): State<AppState>,
     ctx: RequestContext,
     Extension(request_id): Extension<RequestId>,
-    Json(payload): Json<RoleGrantCreate>,
+    JsonBody(payload): JsonBody<RoleGrantCreate>,
 ) -> Result<(StatusCode, Json<RoleGrantResponse>), axum::response::Response> {
     require_role(&ctx, &[Role::Principal])
         .map_err(|error| error.into_response(Some(request_id)))?;
@@ -303,6 +318,14 @@ pub fn addr_from_env(default_port: u16) -> SocketAddr {
     format!("{host}:{port}").parse().expect("valid host:port")
 }
 
+fn pool_from_env() -> Option<sqlx::PgPool> {
+    let database_url = std::env::var("DATABASE_URL").ok()?;
+    sqlx::postgres::PgPoolOptions::new()
+        .max_connections(2)
+        .connect_lazy(&database_url)
+        .ok()
+}
+
 pub async fn check_db() -> Option<sqlx::PgPool> {
     let database_url = match std::env::var("DATABASE_URL") {
         Ok(value) => value,
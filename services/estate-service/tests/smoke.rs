@@ -43,6 +43,29 @@ async fn healthz_exists() {
     assert_eq!(res.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn metrics_serves_policy_denials_without_auth() {
+    init_env();
+    let app = estate_service::router();
+    let req = Request::builder()
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+
+    let res = axum::Router::into_service(app).oneshot(req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers()[header::CONTENT_TYPE],
+        "text/plain; version=0.0.4"
+    );
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(
+        text.contains("# TYPE lifeready_policy_denials_total counter"),
+        "{text}"
+    );
+}
+
 #[tokio::test]
 async fn readyz_exists() {
     init_env();
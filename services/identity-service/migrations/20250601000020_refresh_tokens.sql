@@ -0,0 +1,16 @@
+-- Refresh tokens handed out with each session. Every token belongs to a family that
+-- starts at login; a refresh marks the presented token rotated and issues its successor
+-- in the same family. Presenting an already rotated token revokes the whole family.
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+  token_id uuid PRIMARY KEY,
+  family_id uuid NOT NULL,
+  principal_id text NOT NULL,
+  claims jsonb NOT NULL,
+  issued_at timestamptz NOT NULL DEFAULT now(),
+  expires_at timestamptz NOT NULL,
+  rotated_at timestamptz,
+  replaced_by uuid,
+  revoked_at timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family ON refresh_tokens(family_id);
@@ -0,0 +1,12 @@
+-- Access tokens revoked before their `exp`. Every service's auth layer reloads the
+-- unexpired rows periodically; rows past `expires_at` are reaped since the token would
+-- be rejected on expiry anyway.
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+  jti text PRIMARY KEY,
+  principal_id text NOT NULL,
+  expires_at timestamptz NOT NULL,
+  revoked_by text NOT NULL,
+  revoked_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_revoked_tokens_expires_at ON revoked_tokens(expires_at);
@@ -1,26 +1,32 @@
 use axum::{
     Json, Router,
     extract::{Extension, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::IntoResponse,
     routing::{get, post},
 };
 use chrono::{Duration as ChronoDuration, Utc};
 use lifeready_audit::{AuditEvent, InMemoryAuditSink};
 use lifeready_auth::{
-    AccessLevel, AuthConfig, AuthLayer, Claims, RequestContext, RequestId, Role, SensitivityTier,
+    AccessLevel, AuthConfig, AuthError, AuthLayer, Claims, JsonBody, METRICS_CONTENT_TYPE,
+    METRICS_PATH, RequestContext, RequestId, RevokedTokens, Role, SensitivityTier, access_denied,
     invalid_request, request_id_middleware,
 };
 use lifeready_policy::{TierRequirement, require_role, require_scope, require_tier};
 use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 const TOKEN_TTL_SECONDS: i64 = 900;
+const REFRESH_TOKEN_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
 
 #[derive(Clone)]
 struct AppState {
     audit: InMemoryAuditSink,
     auth: Arc<AuthConfig>,
+    pool: Option<PgPool>,
+    revoked: RevokedTokens,
 }
 
 pub fn router() -> Router {
@@ -28,21 +34,32 @@ pub fn router() -> Router {
         AuthConfig::from_env_checked()
             .expect("AuthConfig misconfigured (check LIFEREADY_ENV and JWT_SECRET)"),
     );
+    let pool = pool_from_env();
+    let revoked = RevokedTokens::from_pool(pool.as_ref());
     let state = AppState {
         audit: InMemoryAuditSink::default(),
         auth: auth.clone(),
+        pool,
+        revoked: revoked.clone(),
     };
 
-    let public_paths = ["/v1/auth/login", "/v1/auth/mfa/verify"];
+    let public_paths = ["/v1/auth/login", "/v1/auth/mfa/verify", "/v1/auth/refresh"];
 
     Router::new()
         .route("/healthz", get(healthz))
         .route("/readyz", get(readyz))
+        .route(METRICS_PATH, get(metrics_text))
         .route("/v1/auth/login", post(login))
         .route("/v1/auth/mfa/verify", post(verify_mfa))
+        .route("/v1/auth/refresh", post(refresh_session))
+        .route("/v1/auth/revoke", post(revoke_token))
         .route("/v1/me", get(me))
         .with_state(state)
-        .layer(AuthLayer::new(auth).with_allowlist(public_paths))
+        .layer(
+            AuthLayer::new(auth)
+                .with_allowlist(public_paths)
+                .with_revocations(revoked),
+        )
         .layer(axum::middleware::from_fn(request_id_middleware))
 }
 
@@ -50,6 +67,14 @@ async fn healthz() -> &'static str {
     "ok"
 }
 
+/// Policy denial counters from `lifeready_policy::metrics`, in Prometheus text format.
+async fn metrics_text() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)],
+        lifeready_policy::metrics::render(),
+    )
+}
+
 async fn readyz() -> (StatusCode, Json<serde_json::Value>) {
     let db_ready = check_db().await.is_some();
     if db_ready {
@@ -94,10 +119,23 @@ struct MfaVerifyRequest {
     code: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct RefreshRequest {
+    refresh_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevokeRequest {
+    token: String,
+}
+
 #[derive(Debug, Serialize)]
 struct Session {
     access_token: String,
     expires_at: String,
+    /// Only issued when the service has a database to track rotation in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -110,7 +148,7 @@ struct MeResponse {
 async fn login(
     State(state): State<AppState>,
     Extension(request_id): Extension<RequestId>,
-    Json(payload): Json<LoginRequest>,
+    JsonBody(payload): JsonBody<LoginRequest>,
 ) -> Result<(StatusCode, Json<LoginChallenge>), axum::response::Response> {
     if payload.email.trim().is_empty() {
         return Err(invalid_request(Some(request_id), "email is required"));
@@ -137,7 +175,7 @@ async fn login(
 async fn verify_mfa(
     State(state): State<AppState>,
     Extension(request_id): Extension<RequestId>,
-    Json(payload): Json<MfaVerifyRequest>,
+    JsonBody(payload): JsonBody<MfaVerifyRequest>,
 ) -> Result<(StatusCode, Json<Session>), axum::response::Response> {
     if payload.challenge_id.trim().is_empty() {
         return Err(invalid_request(
@@ -158,15 +196,38 @@ async fn verify_mfa(
         TOKEN_TTL_SECONDS,
     );
 
-    let token = state
+    let pair = state
         .auth
-        .issue_token(&claims)
+        .issue_token_pair(&claims)
         .map_err(|error| error.into_response(Some(request_id)))?;
 
+    let refresh_token = match &state.pool {
+        Some(pool) => {
+            let mut tx = pool
+                .begin()
+                .await
+                .map_err(|error| db_error_to_response(error, request_id))?;
+            store_refresh_token(
+                &mut tx,
+                pair.refresh_token_id,
+                pair.refresh_token_id,
+                &claims,
+            )
+            .await
+            .map_err(|error| db_error_to_response(error, request_id))?;
+            tx.commit()
+                .await
+                .map_err(|error| db_error_to_response(error, request_id))?;
+            Some(pair.refresh_token)
+        }
+        None => None,
+    };
+
     let expires_at = (Utc::now() + ChronoDuration::seconds(TOKEN_TTL_SECONDS)).to_rfc3339();
     let session = Session {
-        access_token: token,
+        access_token: pair.access_token,
         expires_at,
+        refresh_token,
     };
 
     state.audit.record(AuditEvent::new(
@@ -181,6 +242,284 @@ async fn verify_mfa(
     Ok((StatusCode::OK, Json(session)))
 }
 
+/// Exchange a refresh token for a new session, rotating the refresh token.
+///
+/// A token that was already rotated is evidence the family has leaked, so presenting
+/// one revokes every token in the family, including the legitimate holder's current one.
+async fn refresh_session(
+    State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
+    JsonBody(payload): JsonBody<RefreshRequest>,
+) -> Result<(StatusCode, Json<Session>), axum::response::Response> {
+    let pool = match &state.pool {
+        Some(pool) => pool,
+        None => return Err(invalid_request(Some(request_id), "database unavailable")),
+    };
+    let rejected =
+        || AuthError::unauthorized("refresh token is invalid").into_response(Some(request_id));
+
+    let token_id = state
+        .auth
+        .refresh_token_id(payload.refresh_token.trim())
+        .ok_or_else(rejected)?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|error| db_error_to_response(error, request_id))?;
+
+    let row = sqlx::query(
+        "SELECT family_id, principal_id, claims, expires_at <= now() AS expired, \
+                rotated_at IS NOT NULL AS rotated, revoked_at IS NOT NULL AS revoked \
+         FROM refresh_tokens WHERE token_id = $1 FOR UPDATE",
+    )
+    .bind(token_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| db_error_to_response(error, request_id))?
+    .ok_or_else(rejected)?;
+
+    let family_id: uuid::Uuid = row.get("family_id");
+    let principal_id: String = row.get("principal_id");
+
+    if row.get::<bool, _>("revoked") {
+        return Err(rejected());
+    }
+
+    if row.get::<bool, _>("rotated") {
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = now() \
+             WHERE family_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(family_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|error| db_error_to_response(error, request_id))?;
+        tx.commit()
+            .await
+            .map_err(|error| db_error_to_response(error, request_id))?;
+
+        tracing::warn!(
+            request_id = %request_id.0,
+            %family_id,
+            "rotated refresh token reused; family revoked"
+        );
+        state.audit.record(AuditEvent::new(
+            principal_id,
+            "identity.refresh_token_reused",
+            "green",
+            Some(request_id.0),
+            None,
+            serde_json::json!({"family_id": family_id}),
+        ));
+        return Err(rejected());
+    }
+
+    if row.get::<bool, _>("expired") {
+        return Err(rejected());
+    }
+
+    let previous: Claims = serde_json::from_value(row.get("claims"))
+        .map_err(|_| invalid_request(Some(request_id), "stored session is unreadable"))?;
+    let claims = renewed_claims(&previous);
+
+    let pair = state
+        .auth
+        .issue_token_pair(&claims)
+        .map_err(|error| error.into_response(Some(request_id)))?;
+
+    sqlx::query(
+        "UPDATE refresh_tokens SET rotated_at = now(), replaced_by = $2 WHERE token_id = $1",
+    )
+    .bind(token_id)
+    .bind(pair.refresh_token_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| db_error_to_response(error, request_id))?;
+    store_refresh_token(&mut tx, pair.refresh_token_id, family_id, &claims)
+        .await
+        .map_err(|error| db_error_to_response(error, request_id))?;
+    tx.commit()
+        .await
+        .map_err(|error| db_error_to_response(error, request_id))?;
+
+    state.audit.record(AuditEvent::new(
+        claims.sub.clone(),
+        "identity.session_refreshed",
+        "green",
+        Some(request_id.0),
+        None,
+        serde_json::json!({"family_id": family_id}),
+    ));
+
+    let expires_at = (Utc::now() + ChronoDuration::seconds(TOKEN_TTL_SECONDS)).to_rfc3339();
+    Ok((
+        StatusCode::OK,
+        Json(Session {
+            access_token: pair.access_token,
+            expires_at,
+            refresh_token: Some(pair.refresh_token),
+        }),
+    ))
+}
+
+/// Revoke an access token before its `exp`, along with the refresh token family it was
+/// issued with so the holder cannot mint a replacement. Callers may revoke their own
+/// tokens; administrators may revoke anyone's, e.g. when responding to a leaked credential.
+async fn revoke_token(
+    State(state): State<AppState>,
+    ctx: RequestContext,
+    Extension(request_id): Extension<RequestId>,
+    JsonBody(payload): JsonBody<RevokeRequest>,
+) -> Result<StatusCode, axum::response::Response> {
+    let pool = match &state.pool {
+        Some(pool) => pool,
+        None => return Err(invalid_request(Some(request_id), "database unavailable")),
+    };
+
+    let target = state
+        .auth
+        .decode_token(payload.token.trim())
+        .map_err(|_| invalid_request(Some(request_id), "token is invalid or already expired"))?;
+    if target.sub != ctx.principal_id && !ctx.roles.contains(&Role::Administrator) {
+        return Err(access_denied(
+            Some(request_id),
+            "only administrators may revoke another principal's token",
+        ));
+    }
+
+    let expires_at = chrono::DateTime::from_timestamp(target.exp as i64, 0)
+        .ok_or_else(|| invalid_request(Some(request_id), "token expiry is out of range"))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|error| db_error_to_response(error, request_id))?;
+    sqlx::query(
+        "INSERT INTO revoked_tokens (jti, principal_id, expires_at, revoked_by) \
+         VALUES ($1, $2, $3, $4) ON CONFLICT (jti) DO NOTHING",
+    )
+    .bind(&target.jti)
+    .bind(&target.sub)
+    .bind(expires_at)
+    .bind(&ctx.principal_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| db_error_to_response(error, request_id))?;
+    // Refresh tokens store the claims of the access token issued beside them.
+    let families: Vec<uuid::Uuid> = sqlx::query_scalar(
+        "WITH revoked AS ( \
+           UPDATE refresh_tokens SET revoked_at = now() \
+           WHERE revoked_at IS NULL AND family_id IN \
+             (SELECT family_id FROM refresh_tokens WHERE claims->>'jti' = $1) \
+           RETURNING family_id) \
+         SELECT DISTINCT family_id FROM revoked",
+    )
+    .bind(&target.jti)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|error| db_error_to_response(error, request_id))?;
+    tx.commit()
+        .await
+        .map_err(|error| db_error_to_response(error, request_id))?;
+    state.revoked.revoke(target.jti.clone(), expires_at);
+
+    state.audit.record(AuditEvent::new(
+        ctx.principal_id.clone(),
+        "identity.token_revoked",
+        "green",
+        Some(request_id.0),
+        None,
+        serde_json::json!({
+            "jti": target.jti,
+            "principal_id": target.sub,
+            "refresh_families": families,
+        }),
+    ));
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Interval between revoked-token sweeps, from `REVOCATION_REAPER_INTERVAL_SECS`
+/// (default 1h).
+pub fn revocation_reaper_interval_from_env() -> std::time::Duration {
+    let secs = std::env::var("REVOCATION_REAPER_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(3600);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Periodically deletes revocations whose token has expired on its own.
+pub async fn run_revocation_reaper(pool: PgPool, interval: std::time::Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        match reap_expired_revocations(&pool).await {
+            Ok(0) => {}
+            Ok(reaped) => tracing::info!(reaped, "expired token revocations reaped"),
+            Err(error) => tracing::warn!(error = %error, "token revocation sweep failed"),
+        }
+    }
+}
+
+pub async fn reap_expired_revocations(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= now()")
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Same grant as `previous` with a fresh lifetime and token id. `org_id` is carried over
+/// for tokens minted by a tenant-aware issuer; sign-in here never sets one.
+fn renewed_claims(previous: &Claims) -> Claims {
+    let mut claims = Claims::new(
+        previous.sub.clone(),
+        previous.role,
+        previous.tiers.clone(),
+        previous.access_level,
+        previous.email.clone(),
+        TOKEN_TTL_SECONDS,
+    );
+    claims.scopes = previous.scopes.clone();
+    claims.acting_for = previous.acting_for.clone();
+    claims.org_id = previous.org_id.clone();
+    claims
+}
+
+async fn store_refresh_token(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    token_id: uuid::Uuid,
+    family_id: uuid::Uuid,
+    claims: &Claims,
+) -> Result<(), sqlx::Error> {
+    let stored =
+        serde_json::to_value(claims).map_err(|error| sqlx::Error::Encode(Box::new(error)))?;
+    sqlx::query(
+        "INSERT INTO refresh_tokens (token_id, family_id, principal_id, claims, expires_at) \
+         VALUES ($1, $2, $3, $4, now() + make_interval(secs => $5))",
+    )
+    .bind(token_id)
+    .bind(family_id)
+    .bind(&claims.sub)
+    .bind(stored)
+    .bind(REFRESH_TOKEN_TTL_SECONDS as f64)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+fn db_error_to_response(error: sqlx::Error, request_id: RequestId) -> axum::response::Response {
+    tracing::warn!(
+        request_id = %request_id.0,
+        error = %error,
+        "database error"
+    );
+    invalid_request(Some(request_id), "database operation failed")
+}
+
 async fn me(
     ctx: RequestContext,
     Extension(request_id): Extension<RequestId>,
@@ -219,6 +558,14 @@ pub fn addr_from_env(default_port: u16) -> SocketAddr {
     format!("{host}:{port}").parse().expect("valid host:port")
 }
 
+fn pool_from_env() -> Option<PgPool> {
+    let database_url = std::env::var("DATABASE_URL").ok()?;
+    sqlx::postgres::PgPoolOptions::new()
+        .max_connections(5)
+        .connect_lazy(&database_url)
+        .ok()
+}
+
 pub async fn check_db() -> Option<sqlx::PgPool> {
     let database_url = match std::env::var("DATABASE_URL") {
         Ok(value) => value,
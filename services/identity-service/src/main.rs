@@ -8,7 +8,12 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 async fn main() {
     init_tracing("identity_service=info,tower_http=info");
 
-    let _db = identity_service::check_db().await;
+    if let Some(pool) = identity_service::check_db().await {
+        tokio::spawn(identity_service::run_revocation_reaper(
+            pool,
+            identity_service::revocation_reaper_interval_from_env(),
+        ));
+    }
     let addr = identity_service::addr_from_env(8081);
 
     tracing::info!(%addr, "identity-service listening");
@@ -0,0 +1,304 @@
+#![allow(clippy::await_holding_lock)]
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use lifeready_auth::{AccessLevel, AuthConfig, Claims, Role, SensitivityTier};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::sync::{Mutex, Once};
+use tower::util::ServiceExt;
+
+/// The tests share tables and truncate them, so they run one at a time.
+static DB_LOCK: Mutex<()> = Mutex::new(());
+
+fn init_env() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| unsafe {
+        std::env::set_var("LIFEREADY_ENV", "dev");
+        std::env::set_var("JWT_SECRET", "test-secret-32-chars-minimum!!");
+    });
+}
+
+async fn setup_db() -> Option<PgPool> {
+    let database_url = match std::env::var("DATABASE_URL") {
+        Ok(value) => value,
+        Err(_) => {
+            eprintln!("DATABASE_URL not set; skipping identity-service db tests");
+            return None;
+        }
+    };
+    let pool = PgPool::connect(&database_url).await.ok()?;
+    ensure_schema(&pool).await.ok()?;
+    Some(pool)
+}
+
+async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS refresh_tokens (\
+            token_id uuid PRIMARY KEY,\
+            family_id uuid NOT NULL,\
+            principal_id text NOT NULL,\
+            claims jsonb NOT NULL,\
+            issued_at timestamptz NOT NULL DEFAULT now(),\
+            expires_at timestamptz NOT NULL,\
+            rotated_at timestamptz,\
+            replaced_by uuid,\
+            revoked_at timestamptz\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS revoked_tokens (\
+            jti text PRIMARY KEY,\
+            principal_id text NOT NULL,\
+            expires_at timestamptz NOT NULL,\
+            revoked_by text NOT NULL,\
+            revoked_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn reset_db(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("TRUNCATE refresh_tokens, revoked_tokens")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+fn token_principal(principal_id: &str) -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        principal_id,
+        Role::Principal,
+        vec![SensitivityTier::Green],
+        AccessLevel::ReadOnlyAll,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
+async fn get_me(app: &axum::Router, token: &str) -> StatusCode {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/me")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn post_json(app: &axum::Router, uri: &str, body: Value) -> (StatusCode, Value) {
+    send_json(app, uri, None, body).await
+}
+
+async fn send_json(
+    app: &axum::Router,
+    uri: &str,
+    bearer: Option<&str>,
+    body: Value,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(token) = bearer {
+        request = request.header("authorization", format!("Bearer {token}"));
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn refresh(app: &axum::Router, refresh_token: &str) -> (StatusCode, Value) {
+    post_json(
+        app,
+        "/v1/auth/refresh",
+        serde_json::json!({"refresh_token": refresh_token}),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn refresh_rotates_tokens_and_revokes_family_on_reuse() {
+    init_env();
+    let _guard = DB_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    reset_db(&pool).await.expect("reset db");
+
+    let app = identity_service::router();
+    let (status, session) = post_json(
+        &app,
+        "/v1/auth/mfa/verify",
+        serde_json::json!({"challenge_id": "c-1", "method": "totp", "code": "123456"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let first = session["refresh_token"].as_str().unwrap().to_string();
+
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let principal = config
+        .decode_token(session["access_token"].as_str().unwrap())
+        .unwrap()
+        .sub;
+
+    let (status, rotated) = refresh(&app, &first).await;
+    assert_eq!(status, StatusCode::OK);
+    let second = rotated["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(second, first);
+    let renewed = config
+        .decode_token(rotated["access_token"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(renewed.sub, principal);
+
+    // Replaying the rotated token is treated as theft: it fails and takes the
+    // legitimate successor down with it.
+    let (status, _) = refresh(&app, &first).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = refresh(&app, &second).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let revoked: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM refresh_tokens WHERE principal_id = $1 AND revoked_at IS NOT NULL",
+    )
+    .bind(&principal)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(revoked, 2);
+
+    let forged = format!("{}.{}", uuid::Uuid::new_v4(), "00".repeat(32));
+    let (status, _) = refresh(&app, &forged).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn revoked_token_is_rejected_while_unrelated_tokens_still_work() {
+    init_env();
+    let _guard = DB_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    reset_db(&pool).await.expect("reset db");
+
+    let app = identity_service::router();
+    let leaked = token_principal("00000000-0000-0000-0000-000000000001");
+    let unrelated = token_principal("00000000-0000-0000-0000-000000000002");
+    let bystander = token_principal("00000000-0000-0000-0000-000000000003");
+    assert_eq!(get_me(&app, &leaked).await, StatusCode::OK);
+
+    // Principals may only revoke their own tokens.
+    let (status, _) = send_json(
+        &app,
+        "/v1/auth/revoke",
+        Some(&unrelated),
+        serde_json::json!({"token": bystander}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send_json(
+        &app,
+        "/v1/auth/revoke",
+        Some(&leaked),
+        serde_json::json!({"token": leaked}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    assert_eq!(get_me(&app, &leaked).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(get_me(&app, &unrelated).await, StatusCode::OK);
+    assert_eq!(get_me(&app, &bystander).await, StatusCode::OK);
+
+    // Another replica loads the table before answering its first request.
+    let replica = identity_service::router();
+    assert_eq!(get_me(&replica, &leaked).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(get_me(&replica, &unrelated).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn revoking_an_access_token_revokes_its_refresh_family() {
+    init_env();
+    let _guard = DB_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    reset_db(&pool).await.expect("reset db");
+
+    let app = identity_service::router();
+    let (status, session) = post_json(
+        &app,
+        "/v1/auth/mfa/verify",
+        serde_json::json!({"challenge_id": "c-1", "method": "totp", "code": "123456"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, rotated) = refresh(&app, session["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let access = rotated["access_token"].as_str().unwrap();
+
+    let (status, _) = send_json(
+        &app,
+        "/v1/auth/revoke",
+        Some(access),
+        serde_json::json!({"token": access}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = refresh(&app, rotated["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let live: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM refresh_tokens WHERE revoked_at IS NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(live, 0);
+}
+
+#[tokio::test]
+async fn reaper_drops_revocations_for_expired_tokens() {
+    init_env();
+    let _guard = DB_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    reset_db(&pool).await.expect("reset db");
+
+    sqlx::query(
+        "INSERT INTO revoked_tokens (jti, principal_id, expires_at, revoked_by) VALUES \
+         ('expired', 'p', now() - interval '1 minute', 'p'), \
+         ('live', 'p', now() + interval '1 minute', 'p')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let reaped = identity_service::reap_expired_revocations(&pool)
+        .await
+        .unwrap();
+    assert_eq!(reaped, 1);
+    let remaining: Vec<String> = sqlx::query_scalar("SELECT jti FROM revoked_tokens")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec!["live".to_string()]);
+}
@@ -0,0 +1,6 @@
+-- Soft-delete for documents; versions are retained for audit.
+ALTER TABLE documents ADD COLUMN IF NOT EXISTS deleted_at timestamptz;
+
+CREATE INDEX IF NOT EXISTS idx_documents_principal_live
+  ON documents(principal_id, created_at DESC)
+  WHERE deleted_at IS NULL;
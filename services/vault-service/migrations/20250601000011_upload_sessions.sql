@@ -0,0 +1,15 @@
+-- Resumable chunked uploads. Chunks are stored under uploads/<session>/chunks and
+-- appended strictly in order; abandoned sessions are garbage-collected after 24h.
+CREATE TABLE IF NOT EXISTS upload_sessions (
+  upload_session_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  document_id uuid NOT NULL REFERENCES documents(document_id) ON DELETE CASCADE,
+  principal_id uuid NOT NULL,
+  received_bytes bigint NOT NULL DEFAULT 0,
+  total_bytes bigint,
+  chunk_offsets bigint[] NOT NULL DEFAULT ARRAY[]::bigint[],
+  created_at timestamptz NOT NULL DEFAULT now(),
+  updated_at timestamptz NOT NULL DEFAULT now(),
+  completed_at timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_updated_at ON upload_sessions(updated_at);
@@ -0,0 +1,11 @@
+-- Read access to another principal's documents, recorded when an administrator hands a
+-- deceased principal's vault over to the executor named on an exported estate case.
+CREATE TABLE IF NOT EXISTS document_grants (
+  document_id uuid NOT NULL REFERENCES documents(document_id) ON DELETE CASCADE,
+  grantee_principal_id uuid NOT NULL,
+  case_id uuid NOT NULL,
+  granted_at timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY (document_id, grantee_principal_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_grants_grantee ON document_grants(grantee_principal_id);
@@ -0,0 +1,12 @@
+-- Verdicts from the background integrity sweep, one row per version checked. Kept apart
+-- from document_versions so a tamper that rewrites a version's sha256 does not also
+-- erase the record of earlier checks against the original value.
+CREATE TABLE IF NOT EXISTS integrity_checks (
+  check_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  version_id uuid NOT NULL REFERENCES document_versions(version_id) ON DELETE CASCADE,
+  checked_at timestamptz NOT NULL DEFAULT now(),
+  ok boolean NOT NULL,
+  reason text
+);
+
+CREATE INDEX IF NOT EXISTS idx_integrity_checks_version ON integrity_checks(version_id, checked_at DESC);
@@ -0,0 +1,5 @@
+-- The digest each integrity check compared the blob against. The first check of a
+-- version records document_versions.sha256; later checks carry that value forward, so a
+-- rewritten sha256 keeps failing the sweep instead of becoming the new baseline. Rows
+-- from before this column stay NULL.
+ALTER TABLE integrity_checks ADD COLUMN IF NOT EXISTS sha256 char(64);
@@ -1,6 +1,4 @@
 use axum::Router;
-use std::future::{Future, pending};
-use tokio::net::TcpListener;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -8,12 +6,23 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 async fn main() {
     init_tracing("vault_service=info,tower_http=info");
 
-    let _db = vault_service::check_db().await;
+    if let Some(pool) = vault_service::check_db().await {
+        tokio::spawn(vault_service::run_upload_reaper(
+            pool.clone(),
+            vault_service::upload_reaper_interval_from_env(),
+        ));
+        tokio::spawn(vault_service::run_integrity_sweep(
+            pool,
+            vault_service::storage_from_env(),
+            vault_service::integrity_sweep_interval_from_env(),
+        ));
+    }
     let addr = vault_service::addr_from_env(8083);
 
     tracing::info!(%addr, "vault-service listening");
-    let listener = TcpListener::bind(addr).await.unwrap();
-    run_with_listener(listener, pending()).await.unwrap();
+    lifeready_auth::serve_with_shutdown(build_app(), addr)
+        .await
+        .unwrap();
 }
 
 fn init_tracing(default_filter: &str) {
@@ -30,15 +39,6 @@ fn build_app() -> Router {
     vault_service::router().layer(TraceLayer::new_for_http())
 }
 
-async fn run_with_listener<F>(listener: TcpListener, shutdown: F) -> std::io::Result<()>
-where
-    F: Future<Output = ()> + Send + 'static,
-{
-    axum::serve(listener, build_app())
-        .with_graceful_shutdown(shutdown)
-        .await
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,17 +46,24 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
     };
+    use std::time::Duration;
+    use tokio::net::TcpListener;
     use tokio::sync::oneshot;
     use tower::util::ServiceExt;
 
     #[tokio::test]
-    async fn run_with_listener_stops_on_shutdown() {
+    async fn serve_stops_on_shutdown() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let (tx, rx) = oneshot::channel::<()>();
         let handle = tokio::spawn(async move {
-            run_with_listener(listener, async move {
-                let _ = rx.await;
-            })
+            lifeready_auth::serve_listener_with_shutdown(
+                listener,
+                build_app(),
+                async move {
+                    let _ = rx.await;
+                },
+                Duration::from_secs(1),
+            )
             .await
         });
         let _ = tx.send(());
@@ -6,6 +6,7 @@ use axum::{
 };
 use http_body_util::BodyExt;
 use lifeready_auth::{AccessLevel, AuthConfig, Claims, Role, SensitivityTier};
+use sha2::Digest;
 use sqlx::PgPool;
 use std::future::Future;
 use std::path::PathBuf;
@@ -75,6 +76,49 @@ fn token_read() -> String {
     config.issue_token(&claims).expect("token")
 }
 
+/// Write token holding both Amber and Red, plus any extra scopes.
+fn token_write_red(extra_scopes: &[&str]) -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let mut claims = Claims::new(
+        "00000000-0000-0000-0000-000000000001",
+        Role::Principal,
+        vec![SensitivityTier::Amber, SensitivityTier::Red],
+        AccessLevel::LimitedWrite,
+        None,
+        300,
+    );
+    claims
+        .scopes
+        .extend(extra_scopes.iter().map(|scope| scope.to_string()));
+    config.issue_token(&claims).expect("token")
+}
+
+fn token_executor() -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-000000000002",
+        Role::ExecutorNominee,
+        vec![SensitivityTier::Amber],
+        AccessLevel::ReadOnlyAll,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
+fn token_administrator() -> String {
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-000000000900",
+        Role::Administrator,
+        vec![SensitivityTier::Amber],
+        AccessLevel::LimitedWrite,
+        None,
+        300,
+    );
+    config.issue_token(&claims).expect("token")
+}
+
 fn token_invalid_principal() -> String {
     let config = AuthConfig::new("test-secret-32-chars-minimum!!");
     let claims = Claims::new(
@@ -113,6 +157,17 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     sqlx::query("CREATE EXTENSION IF NOT EXISTS \"uuid-ossp\";")
         .execute(pool)
         .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS revoked_tokens (\
+            jti text PRIMARY KEY,\
+            principal_id text NOT NULL,\
+            expires_at timestamptz NOT NULL,\
+            revoked_by text NOT NULL,\
+            revoked_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
     sqlx::query(
         "DO $$ BEGIN \
          IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'sensitivity_tier') THEN \
@@ -160,6 +215,185 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    sqlx::query("ALTER TABLE documents ADD COLUMN IF NOT EXISTS deleted_at timestamptz;")
+        .execute(pool)
+        .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS audit_events (\
+            event_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            created_at timestamptz NOT NULL DEFAULT now(),\
+            actor_principal_id uuid NOT NULL,\
+            action text NOT NULL,\
+            tier sensitivity_tier NOT NULL,\
+            case_id uuid,\
+            payload jsonb NOT NULL,\
+            prev_hash char(64) NOT NULL,\
+            event_hash char(64) NOT NULL\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS key_epochs (\
+            key_id text PRIMARY KEY,\
+            activated_at timestamptz NOT NULL DEFAULT now(),\
+            retired_at timestamptz\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS key_id text REFERENCES key_epochs(key_id);",
+    )
+    .execute(pool)
+    .await?;
+    // Case tables referenced by the delete guard; owned by case-service's migrations.
+    sqlx::query(
+        "DO $$ BEGIN \
+         IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'case_type') THEN \
+         CREATE TYPE case_type AS ENUM ('emergency_pack','mhca39','death_readiness','will_prep_sa','deceased_estate_reporting_sa'); \
+         END IF; \
+         IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'case_status') THEN \
+         CREATE TYPE case_status AS ENUM ('draft','ready','blocked','exported','closed','revoked'); \
+         END IF; \
+         END $$;",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS cases (\
+            case_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            principal_id uuid NOT NULL,\
+            case_type case_type NOT NULL,\
+            status case_status NOT NULL DEFAULT 'draft',\
+            blocked_reasons text[] NOT NULL DEFAULT ARRAY[]::text[],\
+            created_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query("ALTER TABLE cases ADD COLUMN IF NOT EXISTS archived_at timestamptz;")
+        .execute(pool)
+        .await?;
+    for table in ["case_evidence", "mhca39_evidence"] {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (\
+                evidence_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+                case_id uuid NOT NULL REFERENCES cases(case_id) ON DELETE CASCADE,\
+                slot_name text NOT NULL,\
+                document_id uuid,\
+                added_at timestamptz NOT NULL DEFAULT now(),\
+                UNIQUE(case_id, slot_name)\
+            );"
+        ))
+        .execute(pool)
+        .await?;
+    }
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS emergency_pack_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            directive_document_ids uuid[] NOT NULL DEFAULT ARRAY[]::uuid[],\
+            emergency_contacts jsonb NOT NULL DEFAULT '[]'::jsonb,\
+            share_link_token text,\
+            share_link_expires_at timestamptz,\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS death_readiness_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            executor_nominee_person_id uuid NOT NULL,\
+            asset_document_ids uuid[] NOT NULL DEFAULT ARRAY[]::uuid[],\
+            contact_document_ids uuid[] NOT NULL DEFAULT ARRAY[]::uuid[],\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS deceased_estate_cases (\
+            case_id uuid PRIMARY KEY REFERENCES cases(case_id) ON DELETE CASCADE,\
+            deceased_person_id uuid NOT NULL,\
+            executor_person_id uuid NOT NULL,\
+            estimated_estate_value_zar numeric,\
+            required_evidence_slots text[] NOT NULL,\
+            notes text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    // People and principals are owned by estate-service's migrations.
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS principals (\
+            principal_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            email text UNIQUE NOT NULL,\
+            display_name text,\
+            created_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS people (\
+            person_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            principal_id uuid NOT NULL REFERENCES principals(principal_id) ON DELETE CASCADE,\
+            full_name text NOT NULL,\
+            email text,\
+            phone_e164 text,\
+            relationship text,\
+            created_at timestamptz NOT NULL DEFAULT now()\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "ALTER TABLE people ADD COLUMN IF NOT EXISTS linked_principal_id uuid \
+         REFERENCES principals(principal_id) ON DELETE SET NULL;",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS document_grants (\
+            document_id uuid NOT NULL REFERENCES documents(document_id) ON DELETE CASCADE,\
+            grantee_principal_id uuid NOT NULL,\
+            case_id uuid NOT NULL,\
+            granted_at timestamptz NOT NULL DEFAULT now(),\
+            PRIMARY KEY (document_id, grantee_principal_id)\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS upload_sessions (\
+            upload_session_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            document_id uuid NOT NULL REFERENCES documents(document_id) ON DELETE CASCADE,\
+            principal_id uuid NOT NULL,\
+            received_bytes bigint NOT NULL DEFAULT 0,\
+            total_bytes bigint,\
+            chunk_offsets bigint[] NOT NULL DEFAULT ARRAY[]::bigint[],\
+            created_at timestamptz NOT NULL DEFAULT now(),\
+            updated_at timestamptz NOT NULL DEFAULT now(),\
+            completed_at timestamptz\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS integrity_checks (\
+            check_id uuid PRIMARY KEY DEFAULT uuid_generate_v4(),\
+            version_id uuid NOT NULL REFERENCES document_versions(version_id) ON DELETE CASCADE,\
+            checked_at timestamptz NOT NULL DEFAULT now(),\
+            ok boolean NOT NULL,\
+            reason text\
+        );",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query("ALTER TABLE integrity_checks ADD COLUMN IF NOT EXISTS sha256 char(64);")
+        .execute(pool)
+        .await?;
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_documents_principal ON documents(principal_id);")
         .execute(pool)
         .await?;
@@ -172,9 +406,11 @@ async fn ensure_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
 }
 
 async fn reset_db(pool: &PgPool) -> Result<(), sqlx::Error> {
-    sqlx::query("TRUNCATE document_versions, documents RESTART IDENTITY CASCADE")
-        .execute(pool)
-        .await?;
+    sqlx::query(
+        "TRUNCATE document_versions, documents, people, principals RESTART IDENTITY CASCADE",
+    )
+    .execute(pool)
+    .await?;
     Ok(())
 }
 
@@ -219,16 +455,15 @@ async fn init_document_commit_and_list() {
         let blob_path = storage_dir.join(document_id);
         std::fs::write(&blob_path, b"blob").unwrap();
 
-        let sha256 = "a".repeat(64);
-        let commit_body = serde_json::json!({
-            "blob_ref": "auto",
-            "sha256": sha256,
-            "byte_size": 4,
-            "mime_type": "text/plain"
-        })
-        .to_string();
-        let response = axum::Router::into_service(app.clone())
-            .oneshot(
+        let commit = |sha256: &str| {
+            let commit_body = serde_json::json!({
+                "blob_ref": "auto",
+                "sha256": sha256,
+                "byte_size": 4,
+                "mime_type": "text/plain"
+            })
+            .to_string();
+            axum::Router::into_service(app.clone()).oneshot(
                 Request::builder()
                     .method("POST")
                     .uri(format!("/v1/documents/{document_id}/versions"))
@@ -237,9 +472,22 @@ async fn init_document_commit_and_list() {
                     .body(Body::from(commit_body))
                     .unwrap(),
             )
-            .await
-            .unwrap();
+        };
+
+        // The digest is recomputed from the staged bytes; a client claim that does not
+        // match is rejected and leaves the staged upload in place.
+        let response = commit(&"a".repeat(64)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            problem["detail"],
+            "sha256 does not match the uploaded content"
+        );
+        assert!(blob_path.exists());
 
+        let sha256 = hex::encode(sha2::Sha256::digest(b"blob"));
+        let response = commit(&sha256).await.unwrap();
         assert_eq!(response.status(), StatusCode::CREATED);
         let body = response.into_body().collect().await.unwrap().to_bytes();
         let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -248,6 +496,7 @@ async fn init_document_commit_and_list() {
             Some(sha256.as_str())
         );
         assert!(value.get("version_id").is_some());
+        assert!(!blob_path.exists(), "staged upload is removed after commit");
 
         let response = axum::Router::into_service(app)
             .oneshot(
@@ -274,6 +523,72 @@ async fn init_document_commit_and_list() {
     .await;
 }
 
+#[tokio::test]
+async fn sensitivity_policy_forces_wills_to_amber() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-policy");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let policy_path = storage_dir.join("sensitivity-policy.toml");
+    std::fs::write(&policy_path, "[will]\nmin_sensitivity = \"amber\"\n").unwrap();
+
+    let config = AuthConfig::new("test-secret-32-chars-minimum!!");
+    let claims = Claims::new(
+        "00000000-0000-0000-0000-000000000001",
+        Role::Principal,
+        vec![SensitivityTier::Green, SensitivityTier::Amber],
+        AccessLevel::LimitedWrite,
+        None,
+        300,
+    );
+    let token = config.issue_token(&claims).expect("token");
+
+    with_env_async(
+        &[
+            ("LOCAL_STORAGE_DIR", storage_dir.to_str()),
+            ("SENSITIVITY_POLICY", policy_path.to_str()),
+        ],
+        || async {
+            let app = vault_service::router();
+            let mut statuses = Vec::new();
+            for sensitivity in ["green", "amber"] {
+                let body = serde_json::json!({
+                    "document_type": "will",
+                    "title": "My will",
+                    "sensitivity": sensitivity
+                })
+                .to_string();
+                let response = axum::Router::into_service(app.clone())
+                    .oneshot(
+                        Request::builder()
+                            .method("POST")
+                            .uri("/v1/documents")
+                            .header("content-type", "application/json")
+                            .header("authorization", format!("Bearer {}", token))
+                            .body(Body::from(body))
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                statuses.push(response.status());
+            }
+            assert_eq!(statuses, [StatusCode::BAD_REQUEST, StatusCode::CREATED]);
+
+            let tiers: Vec<String> = sqlx::query_scalar("SELECT sensitivity::text FROM documents")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(tiers, ["amber"]);
+        },
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn get_document_returns_payload() {
     init_env();
@@ -443,7 +758,7 @@ async fn commit_document_rejects_missing_blob() {
 }
 
 #[tokio::test]
-async fn commit_document_rejects_duplicate_version() {
+async fn commit_document_rejects_sniffed_mime_mismatch() {
     init_env();
     let pool = match setup_db().await {
         Some(pool) => pool,
@@ -480,33 +795,39 @@ async fn commit_document_rejects_duplicate_version() {
         let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
         let document_id = value.get("document_id").and_then(|v| v.as_str()).unwrap();
 
-        let blob_path = storage_dir.join(document_id);
-        std::fs::write(&blob_path, b"blob").unwrap();
-
-        let sha256 = "a".repeat(64);
-        let commit_body = serde_json::json!({
-            "blob_ref": "auto",
-            "sha256": sha256,
-            "byte_size": 4,
-            "mime_type": "text/plain"
-        })
-        .to_string();
-
-        let response = axum::Router::into_service(app.clone())
-            .oneshot(
-                Request::builder()
-                    .method("POST")
-                    .uri(format!("/v1/documents/{document_id}/versions"))
-                    .header("content-type", "application/json")
-                    .header("authorization", format!("Bearer {}", token_write()))
-                    .body(Body::from(commit_body.clone()))
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(response.status(), StatusCode::CREATED);
+        // A Windows executable header labelled as a PDF.
+        let executable = b"MZ\x90\x00\x03\x00\x00\x00";
+        std::fs::write(storage_dir.join(document_id), executable).unwrap();
+
+        let commit = |mime_type: &str, contents: &[u8]| {
+            serde_json::json!({
+                "blob_ref": "auto",
+                "sha256": hex::encode(sha2::Sha256::digest(contents)),
+                "byte_size": contents.len(),
+                "mime_type": mime_type
+            })
+            .to_string()
+        };
+        for mime_type in ["application/pdf", "text/plain"] {
+            let response = axum::Router::into_service(app.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(format!("/v1/documents/{document_id}/versions"))
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {}", token_write()))
+                        .body(Body::from(commit(mime_type, executable)))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            assert!(String::from_utf8_lossy(&body).contains("blob content looks like"));
+        }
 
+        let pdf = b"%PDF-1.4\n%%EOF\n";
+        std::fs::write(storage_dir.join(document_id), pdf).unwrap();
         let response = axum::Router::into_service(app)
             .oneshot(
                 Request::builder()
@@ -514,19 +835,18 @@ async fn commit_document_rejects_duplicate_version() {
                     .uri(format!("/v1/documents/{document_id}/versions"))
                     .header("content-type", "application/json")
                     .header("authorization", format!("Bearer {}", token_write()))
-                    .body(Body::from(commit_body))
+                    .body(Body::from(commit("application/pdf", pdf)))
                     .unwrap(),
             )
             .await
             .unwrap();
-
-        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.status(), StatusCode::CREATED);
     })
     .await;
 }
 
 #[tokio::test]
-async fn get_document_returns_not_found() {
+async fn commit_document_deduplicates_identical_content() {
     init_env();
     let pool = match setup_db().await {
         Some(pool) => pool,
@@ -539,26 +859,81 @@ async fn get_document_returns_not_found() {
 
     with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
         let app = vault_service::router();
-        let document_id = uuid::Uuid::new_v4();
-        let response = axum::Router::into_service(app)
-            .oneshot(
-                Request::builder()
-                    .method("GET")
-                    .uri(format!("/v1/documents/{document_id}"))
-                    .header("authorization", format!("Bearer {}", token_read()))
-                    .body(Body::empty())
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
+        let contents = b"identical will contents";
+        let content_sha = hex::encode(sha2::Sha256::digest(contents));
+
+        let mut blob_refs = Vec::new();
+        for title in ["Original will", "Copy of will"] {
+            let body = serde_json::json!({
+                "document_type": "will",
+                "title": title,
+                "sensitivity": "amber"
+            })
+            .to_string();
+            let response = axum::Router::into_service(app.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/v1/documents")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {}", token_write()))
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let document_id: Uuid = value["document_id"].as_str().unwrap().parse().unwrap();
+
+            std::fs::write(storage_dir.join(document_id.to_string()), contents).unwrap();
+            let commit = serde_json::json!({
+                "blob_ref": "auto",
+                "sha256": content_sha,
+                "byte_size": contents.len(),
+                "mime_type": "text/plain"
+            })
+            .to_string();
+            let response = axum::Router::into_service(app.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(format!("/v1/documents/{document_id}/versions"))
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {}", token_write()))
+                        .body(Body::from(commit))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            let blob_ref: String =
+                sqlx::query_scalar("SELECT blob_ref FROM document_versions WHERE document_id = $1")
+                    .bind(document_id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            blob_refs.push(blob_ref);
+        }
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(blob_refs[0], blob_refs[1]);
+        let expected = storage_dir
+            .join("blobs")
+            .join(&content_sha[..2])
+            .join(&content_sha);
+        assert_eq!(
+            blob_refs[0],
+            format!("blobs/{}/{content_sha}", &content_sha[..2])
+        );
+        assert_eq!(std::fs::read(&expected).unwrap(), contents);
     })
     .await;
 }
 
 #[tokio::test]
-async fn list_documents_filters_unavailable_tiers() {
+async fn integrity_sweep_records_results_and_serves_history() {
     init_env();
     let pool = match setup_db().await {
         Some(pool) => pool,
@@ -566,38 +941,413 @@ async fn list_documents_filters_unavailable_tiers() {
     };
     reset_db(&pool).await.unwrap();
 
-    let storage_dir = unique_dir("vault-storage");
+    let storage_dir = unique_dir("vault-sweep");
     std::fs::create_dir_all(&storage_dir).unwrap();
-
-    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
-        let principal_id = uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
-        sqlx::query(
-            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
-             VALUES ($1, $2, 'id', $3, 'amber', ARRAY[]::text[])",
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'Will', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let mut version_ids = Vec::new();
+    for (name, contents) in [("intact", &b"intact"[..]), ("tampered", &b"original"[..])] {
+        let path = storage_dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        let version_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+             VALUES ($1, $2, $3, $4, 'text/plain') RETURNING version_id",
         )
-        .bind(uuid::Uuid::new_v4())
-        .bind(principal_id)
-        .bind("Amber Doc")
-        .execute(&pool)
+        .bind(document_id)
+        .bind(path.to_string_lossy().to_string())
+        .bind(hex::encode(sha2::Sha256::digest(contents)))
+        .bind(contents.len() as i64)
+        .fetch_one(&pool)
         .await
         .unwrap();
+        version_ids.push(version_id);
+    }
+    std::fs::write(storage_dir.join("tampered"), b"rewritten").unwrap();
 
-        sqlx::query(
-            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
-             VALUES ($1, $2, 'id', $3, 'red', ARRAY[]::text[])",
-        )
-        .bind(uuid::Uuid::new_v4())
-        .bind(principal_id)
-        .bind("Red Doc")
-        .execute(&pool)
+    let storage = vault_service::LocalFsStorage::new(storage_dir.clone());
+    let mismatches_before = vault_service::metrics::integrity_mismatches_total();
+    let sweep = vault_service::sweep_integrity(&pool, &storage, 1)
+        .await
+        .unwrap();
+    assert_eq!(sweep.checked, 1);
+    // The next sweep starts with the version the first one has not reached yet.
+    let sweep = vault_service::sweep_integrity(&pool, &storage, 1)
         .await
         .unwrap();
+    assert_eq!(sweep.checked, 1);
+    let checked: Vec<Uuid> =
+        sqlx::query_scalar("SELECT version_id FROM integrity_checks ORDER BY checked_at")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(checked.len(), 2);
+    assert_ne!(checked[0], checked[1]);
+    assert_eq!(
+        vault_service::metrics::integrity_mismatches_total() - mismatches_before,
+        1
+    );
 
-        let app = vault_service::router();
-        let response = axum::Router::into_service(app)
-            .oneshot(
-                Request::builder()
-                    .method("GET")
+    let app = vault_service::router();
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/documents/{document_id}/integrity-history"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let items = value["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    for item in items {
+        let intact = item["version_id"] == version_ids[0].to_string();
+        assert_eq!(item["ok"], intact);
+        assert_eq!(item["sha256"].as_str().map(str::len), Some(64));
+        if !intact {
+            assert_eq!(item["reason"], "sha256 mismatch");
+        }
+    }
+
+    // Rewriting the blob and its recorded digest together still fails against the
+    // digest earlier checks recorded.
+    let forged = hex::encode(sha2::Sha256::digest(b"forged"));
+    std::fs::write(storage_dir.join("intact"), b"forged").unwrap();
+    sqlx::query("UPDATE document_versions SET sha256 = $2 WHERE version_id = $1")
+        .bind(version_ids[0])
+        .bind(&forged)
+        .execute(&pool)
+        .await
+        .unwrap();
+    vault_service::sweep_integrity(&pool, &storage, 1)
+        .await
+        .unwrap();
+    let (ok, reason, recorded): (bool, Option<String>, String) = sqlx::query_as(
+        "SELECT ok, reason, sha256 FROM integrity_checks WHERE version_id = $1 \
+         ORDER BY checked_at DESC LIMIT 1",
+    )
+    .bind(version_ids[0])
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(!ok);
+    assert_eq!(
+        reason.as_deref(),
+        Some("sha256 changed since the last recorded check")
+    );
+    assert_eq!(recorded, hex::encode(sha2::Sha256::digest(b"intact")));
+
+    let response = axum::Router::into_service(vault_service::router())
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(text.contains(&format!(
+        "lifeready_vault_integrity_checks_total{{result=\"mismatch\"}} {}",
+        vault_service::metrics::integrity_mismatches_total()
+    )));
+}
+
+#[tokio::test]
+async fn verify_integrity_flags_tampered_blobs() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-storage");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let send = |method: &str, uri: String, token: String, body: Body| {
+            axum::Router::into_service(app.clone()).oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(body)
+                    .unwrap(),
+            )
+        };
+
+        let mut document_ids = Vec::new();
+        for contents in [&b"first document"[..], &b"second document"[..]] {
+            let response = send(
+                "POST",
+                "/v1/documents".to_string(),
+                token_write(),
+                Body::from(
+                    serde_json::json!({
+                        "document_type": "will",
+                        "title": "Will",
+                        "sensitivity": "amber"
+                    })
+                    .to_string(),
+                ),
+            )
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let document_id = value["document_id"].as_str().unwrap().to_string();
+
+            std::fs::write(storage_dir.join(&document_id), contents).unwrap();
+            let response = send(
+                "POST",
+                format!("/v1/documents/{document_id}/versions"),
+                token_write(),
+                Body::from(
+                    serde_json::json!({
+                        "blob_ref": "auto",
+                        "sha256": hex::encode(sha2::Sha256::digest(contents)),
+                        "byte_size": contents.len(),
+                        "mime_type": "text/plain"
+                    })
+                    .to_string(),
+                ),
+            )
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            document_ids.push(document_id);
+        }
+
+        let response = send(
+            "POST",
+            format!("/v1/documents/{}/verify", document_ids[0]),
+            token_read(),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["ok"], true);
+        assert_eq!(report["versions"][0]["ok"], true);
+
+        let blob_ref: String = sqlx::query_scalar(
+            "SELECT blob_ref FROM document_versions WHERE document_id = $1::uuid",
+        )
+        .bind(&document_ids[1])
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        std::fs::write(storage_dir.join(blob_ref), b"tampered").unwrap();
+
+        let response = send(
+            "POST",
+            "/v1/documents/verify".to_string(),
+            token_read(),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["ok"], false);
+        let documents = report["documents"].as_array().unwrap();
+        assert_eq!(documents.len(), 2);
+        let tampered = documents
+            .iter()
+            .find(|document| document["document_id"] == document_ids[1].as_str())
+            .unwrap();
+        assert_eq!(tampered["versions"][0]["ok"], false);
+        assert_eq!(tampered["versions"][0]["reason"], "sha256 mismatch");
+
+        let response = send(
+            "POST",
+            format!("/v1/documents/{}/verify", document_ids[0]),
+            token_write(),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn commit_document_rejects_duplicate_version() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-storage");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let body = serde_json::json!({
+            "document_type": "will",
+            "title": "My will",
+            "sensitivity": "amber"
+        })
+        .to_string();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/documents")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let document_id = value.get("document_id").and_then(|v| v.as_str()).unwrap();
+
+        let blob_path = storage_dir.join(document_id);
+        std::fs::write(&blob_path, b"blob").unwrap();
+
+        let sha256 = hex::encode(sha2::Sha256::digest(b"blob"));
+        let commit_body = serde_json::json!({
+            "blob_ref": "auto",
+            "sha256": sha256,
+            "byte_size": 4,
+            "mime_type": "text/plain"
+        })
+        .to_string();
+
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/documents/{document_id}/versions"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(commit_body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // The commit consumed the staged upload; stage the same bytes again.
+        std::fs::write(&blob_path, b"blob").unwrap();
+        let response = axum::Router::into_service(app)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/documents/{document_id}/versions"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(commit_body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn get_document_returns_not_found() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-storage");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let document_id = uuid::Uuid::new_v4();
+        let response = axum::Router::into_service(app)
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/documents/{document_id}"))
+                    .header("authorization", format!("Bearer {}", token_read()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn list_documents_filters_unavailable_tiers() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-storage");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let principal_id = uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+        sqlx::query(
+            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+             VALUES ($1, $2, 'id', $3, 'amber', ARRAY[]::text[])",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(principal_id)
+        .bind("Amber Doc")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO documents (document_id, principal_id, document_type, title, sensitivity, tags) \
+             VALUES ($1, $2, 'id', $3, 'red', ARRAY[]::text[])",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(principal_id)
+        .bind("Red Doc")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let app = vault_service::router();
+        let response = axum::Router::into_service(app)
+            .oneshot(
+                Request::builder()
+                    .method("GET")
                     .uri("/v1/documents?limit=10")
                     .header("authorization", format!("Bearer {}", token_read()))
                     .body(Body::empty())
@@ -826,9 +1576,11 @@ async fn commit_document_accepts_file_ref() {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::CREATED);
+        let location = response.headers()["location"].to_str().unwrap().to_string();
         let body = response.into_body().collect().await.unwrap().to_bytes();
         let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
         let document_id = value.get("document_id").and_then(|v| v.as_str()).unwrap();
+        assert_eq!(location, format!("/v1/documents/{document_id}"));
 
         let blob_path = storage_dir.join("explicit-blob");
         std::fs::write(&blob_path, b"blob").unwrap();
@@ -836,7 +1588,7 @@ async fn commit_document_accepts_file_ref() {
 
         let commit_body = serde_json::json!({
             "blob_ref": blob_ref,
-            "sha256": "a".repeat(64),
+            "sha256": hex::encode(sha2::Sha256::digest(b"blob")),
             "byte_size": 4,
             "mime_type": "text/plain"
         })
@@ -855,6 +1607,16 @@ async fn commit_document_accepts_file_ref() {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::CREATED);
+        let location = response.headers()["location"].to_str().unwrap().to_string();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            location,
+            format!(
+                "/v1/documents/{document_id}/download?version_id={}",
+                value["version_id"].as_str().unwrap()
+            )
+        );
     })
     .await;
 }
@@ -1098,3 +1860,1559 @@ async fn list_documents_rejects_insufficient_role() {
 
     assert_eq!(response.status(), StatusCode::FORBIDDEN);
 }
+
+#[tokio::test]
+async fn signed_download_url_serves_document_without_bearer() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-signed");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let blob_path = storage_dir.join("signed-blob");
+    std::fs::write(&blob_path, b"hello").unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'My will', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, '2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824', 5, 'text/plain')",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    with_env_async(
+        &[
+            ("LOCAL_STORAGE_DIR", storage_dir.to_str()),
+            ("VAULT_PUBLIC_BASE_URL", Some("https://vault.example.org/")),
+        ],
+        || async {
+            let app = vault_service::router();
+            let response = axum::Router::into_service(app.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(format!("/v1/documents/{document_id}/sign"))
+                        .header("authorization", format!("Bearer {}", token_write()))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let url = value["url"].as_str().unwrap();
+            assert!(
+                url.starts_with(&format!(
+                    "https://vault.example.org/v1/documents/{document_id}/download?"
+                )),
+                "{url}"
+            );
+            let path = &url[url.find("/v1/").unwrap()..];
+
+            let response = axum::Router::into_service(app.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(path)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(&body[..], b"hello");
+
+            let tampered = path.replace("&exp=", "&exp=1");
+            let response = axum::Router::into_service(app)
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(tampered)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn download_document_honors_etag_preconditions() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-etag");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let blob_path = storage_dir.join("etag-blob");
+    std::fs::write(&blob_path, b"hello").unwrap();
+    let sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'My will', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 5, 'text/plain')",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/documents/{document_id}/sign"))
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let url = value["url"].as_str().unwrap();
+        let path = url[url.find("/v1/").unwrap()..].to_string();
+        let download = |header: Option<(&'static str, String)>| {
+            let mut request = Request::builder().method("GET").uri(path.clone());
+            if let Some((name, value)) = header {
+                request = request.header(name, value);
+            }
+            axum::Router::into_service(app.clone()).oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = download(None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["etag"], format!("\"{sha256}\""));
+
+        let response = download(Some(("if-none-match", format!("\"{sha256}\""))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()["etag"], format!("\"{sha256}\""));
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+
+        let response = download(Some(("if-none-match", "\"stale\"".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = download(Some(("if-match", "\"stale\"".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn download_document_serves_byte_ranges() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-range");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let blob_path = storage_dir.join("range-blob");
+    std::fs::write(&blob_path, b"hello").unwrap();
+    let sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'My will', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+         VALUES ($1, $2, $3, 5, 'text/plain')",
+    )
+    .bind(document_id)
+    .bind(format!("file://{}", blob_path.display()))
+    .bind(sha256)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/documents/{document_id}/sign"))
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let url = value["url"].as_str().unwrap();
+        let path = url[url.find("/v1/").unwrap()..].to_string();
+        let download = |header: Option<(&'static str, String)>| {
+            let mut request = Request::builder().method("GET").uri(path.clone());
+            if let Some((name, value)) = header {
+                request = request.header(name, value);
+            }
+            axum::Router::into_service(app.clone()).oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = download(Some(("range", "bytes=1-3".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()["content-range"], "bytes 1-3/5");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"ell");
+
+        let response = download(Some(("range", "bytes=-2".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"lo");
+
+        let response = download(Some(("range", "bytes=5-".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()["content-range"], "bytes */5");
+
+        let response = download(None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["accept-ranges"], "bytes");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn compare_versions_reports_text_diff_and_binary_metadata() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-diff");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'My will', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let mut version_ids = Vec::new();
+    for (name, content, mime_type) in [
+        ("v1", "alpha\nbeta\n", "text/plain"),
+        ("v2", "alpha\ngamma\n", "text/plain"),
+        ("v3", "\u{0}\u{1}", "application/pdf"),
+    ] {
+        let blob_path = storage_dir.join(name);
+        std::fs::write(&blob_path, content).unwrap();
+        let version_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING version_id",
+        )
+        .bind(document_id)
+        .bind(format!("file://{}", blob_path.display()))
+        .bind(hex::encode(sha2::Sha256::digest(content.as_bytes())))
+        .bind(content.len() as i64)
+        .bind(mime_type)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        version_ids.push(version_id);
+    }
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let compare = |from: Uuid, to: Uuid| {
+            let app = app.clone();
+            async move {
+                let response = axum::Router::into_service(app)
+                    .oneshot(
+                        Request::builder()
+                            .method("GET")
+                            .uri(format!(
+                                "/v1/documents/{document_id}/diff?from={from}&to={to}"
+                            ))
+                            .header("authorization", format!("Bearer {}", token_read()))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let text = compare(version_ids[0], version_ids[1]).await;
+        assert_eq!(text["identical"], false);
+        assert_eq!(text["byte_size_delta"], 1);
+        let diff = text["diff"].as_str().unwrap();
+        assert!(diff.contains("-beta"));
+        assert!(diff.contains("+gamma"));
+
+        let binary = compare(version_ids[1], version_ids[2]).await;
+        assert!(binary.get("diff").is_none());
+        assert_eq!(binary["diff_skipped_reason"], "binary content");
+
+        let same = compare(version_ids[0], version_ids[0]).await;
+        assert_eq!(same["identical"], true);
+        assert_eq!(same["byte_size_delta"], 0);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn chunked_upload_assembles_in_order_and_commits() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-chunked");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'statement', 'Inventory', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let send = |method: &'static str,
+                    uri: String,
+                    range: Option<&'static str>,
+                    body: &'static [u8]| {
+            let app = app.clone();
+            async move {
+                let mut request = Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("authorization", format!("Bearer {}", token_write()));
+                if let Some(range) = range {
+                    request = request.header("content-range", range);
+                }
+                let response = axum::Router::into_service(app)
+                    .oneshot(request.body(Body::from(body)).unwrap())
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, session) = send(
+            "POST",
+            format!("/v1/documents/{document_id}/uploads"),
+            None,
+            b"",
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let session_uri = format!(
+            "/v1/documents/{document_id}/uploads/{}",
+            session["upload_session_id"].as_str().unwrap()
+        );
+
+        let (status, _) = send("PATCH", session_uri.clone(), Some("bytes 0-4/10"), b"hello").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send("PATCH", session_uri.clone(), Some("bytes 3-6/10"), b"lo w").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = send("PATCH", session_uri.clone(), Some("bytes 7-9/10"), b"rld").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = send("POST", format!("{session_uri}/complete"), None, b"").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, progress) =
+            send("PATCH", session_uri.clone(), Some("bytes 5-9/10"), b"world").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(progress["received_bytes"], 10);
+
+        let (status, completed) = send("POST", format!("{session_uri}/complete"), None, b"").await;
+        assert_eq!(status, StatusCode::OK);
+        let sha256 = hex::encode(sha2::Sha256::digest(b"helloworld"));
+        assert_eq!(completed["sha256"], sha256.as_str());
+        assert_eq!(completed["byte_size"], 10);
+
+        let commit = serde_json::json!({
+            "blob_ref": completed["blob_ref"],
+            "sha256": sha256,
+            "byte_size": 10,
+            "mime_type": "text/plain",
+        });
+        let response = axum::Router::into_service(app.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/v1/documents/{document_id}/versions"))
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(commit.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn upload_reaper_drops_abandoned_sessions_and_chunks() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-upload-reaper");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let storage = vault_service::LocalFsStorage::new(storage_dir.clone());
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'statement', 'Inventory', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let session_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO upload_sessions (document_id, principal_id, received_bytes, chunk_offsets, updated_at) \
+         VALUES ($1, '00000000-0000-0000-0000-000000000001', 5, ARRAY[0]::bigint[], now() - interval '2 days') \
+         RETURNING upload_session_id",
+    )
+    .bind(document_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let chunk_key = format!("uploads/{session_id}/chunks/{:020}", 0);
+    vault_service::Storage::put(&storage, &chunk_key, b"hello")
+        .await
+        .unwrap();
+
+    let reaped = vault_service::reap_abandoned_uploads(&pool, &storage)
+        .await
+        .unwrap();
+    assert_eq!(reaped, 1);
+    assert!(!storage_dir.join(&chunk_key).exists());
+}
+
+#[tokio::test]
+async fn delete_document_is_blocked_by_active_cases_then_hides_document() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'id', 'ID copy', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'mhca39', 'blocked') RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO mhca39_evidence (case_id, slot_name, document_id) VALUES ($1, 'applicant_id_copy', $2)",
+    )
+    .bind(case_id)
+    .bind(document_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let app = vault_service::router();
+    let delete = || {
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/v1/documents/{document_id}"))
+            .header("authorization", format!("Bearer {}", token_write()))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(delete())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body).contains(&case_id.to_string()));
+
+    sqlx::query("UPDATE cases SET archived_at = now() WHERE case_id = $1")
+        .bind(case_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(delete())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(delete())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/documents/{document_id}"))
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let events: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM audit_events WHERE action = 'document.deleted' \
+         AND payload->>'document_id' = $1",
+    )
+    .bind(document_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+}
+
+#[tokio::test]
+async fn delete_document_is_blocked_by_death_readiness_references() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'other', 'Contacts', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'death_readiness', 'draft') RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO death_readiness_cases (case_id, executor_nominee_person_id, contact_document_ids) \
+         VALUES ($1, uuid_generate_v4(), $2)",
+    )
+    .bind(case_id)
+    .bind(vec![document_id])
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let response = axum::Router::into_service(vault_service::router())
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/v1/documents/{document_id}"))
+                .header("authorization", format!("Bearer {}", token_write()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body).contains(&case_id.to_string()));
+    let deleted: bool =
+        sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM documents WHERE document_id = $1")
+            .bind(document_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!deleted);
+}
+
+fn reclassify_request(document_id: Uuid, token: &str, sensitivity: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(format!("/v1/documents/{document_id}/reclassify"))
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::from(
+            serde_json::json!({"sensitivity": sensitivity}).to_string(),
+        ))
+        .unwrap()
+}
+
+async fn document_sensitivity(pool: &PgPool, document_id: Uuid) -> String {
+    sqlx::query_scalar("SELECT sensitivity::text FROM documents WHERE document_id = $1")
+        .bind(document_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn reclassify_document_upgrades_with_audit() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'id', 'ID copy', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let app = vault_service::router();
+
+    // Amber-only callers cannot move a document into Red.
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(reclassify_request(document_id, &token_write(), "red"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(document_sensitivity(&pool, document_id).await, "amber");
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(reclassify_request(
+            document_id,
+            &token_write_red(&[]),
+            "red",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["sensitivity"], "red");
+    assert_eq!(document_sensitivity(&pool, document_id).await, "red");
+
+    let row: (String, serde_json::Value) = sqlx::query_as(
+        "SELECT tier::text, payload FROM audit_events WHERE action = 'document.reclassified' \
+         AND payload->>'document_id' = $1",
+    )
+    .bind(document_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(row.0, "red");
+    assert_eq!(row.1["from"], "amber");
+    assert_eq!(row.1["to"], "red");
+
+    let response = axum::Router::into_service(app)
+        .oneshot(reclassify_request(
+            document_id,
+            &token_write_red(&[]),
+            "red",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn reclassify_document_downgrade_requires_declassify_scope() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'id', 'ID copy', 'red') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let app = vault_service::router();
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(reclassify_request(
+            document_id,
+            &token_write_red(&[]),
+            "amber",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(document_sensitivity(&pool, document_id).await, "red");
+    let events: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM audit_events WHERE action = 'document.reclassified' \
+         AND payload->>'document_id' = $1",
+    )
+    .bind(document_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 0);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(reclassify_request(
+            document_id,
+            &token_write_red(&["write:declassify"]),
+            "amber",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(document_sensitivity(&pool, document_id).await, "amber");
+}
+
+#[tokio::test]
+async fn reclassify_document_downgrade_respects_policy_floor() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'My will', 'red') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let policy_path =
+        std::env::temp_dir().join(format!("sensitivity-policy-{}.toml", Uuid::new_v4()));
+    std::fs::write(&policy_path, "[will]\nmin_sensitivity = \"amber\"\n").unwrap();
+    let mut app = None;
+    with_env_async(&[("SENSITIVITY_POLICY", policy_path.to_str())], || async {
+        app = Some(vault_service::router());
+    })
+    .await;
+    std::fs::remove_file(&policy_path).ok();
+    let app = app.unwrap();
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(reclassify_request(
+            document_id,
+            &token_write_red(&["write:declassify"]),
+            "green",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(document_sensitivity(&pool, document_id).await, "red");
+
+    let response = axum::Router::into_service(app)
+        .oneshot(reclassify_request(
+            document_id,
+            &token_write_red(&["write:declassify"]),
+            "amber",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(document_sensitivity(&pool, document_id).await, "amber");
+}
+
+#[tokio::test]
+async fn transfer_documents_grants_executor_read_access() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let document_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+         VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'My will', 'amber') \
+         RETURNING document_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let case_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO cases (principal_id, case_type, status) \
+         VALUES ('00000000-0000-0000-0000-000000000002', 'deceased_estate_reporting_sa', 'draft') \
+         RETURNING case_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    // People are keyed independently of the principals they sign in as; the contact
+    // records belong to the principal who opened the case.
+    let deceased_person = Uuid::parse_str("00000000-0000-0000-0000-00000000d001").unwrap();
+    let executor_person = Uuid::parse_str("00000000-0000-0000-0000-00000000e002").unwrap();
+    sqlx::query(
+        "INSERT INTO principals (principal_id, email) VALUES \
+         ('00000000-0000-0000-0000-000000000001', 'deceased@example.com'), \
+         ('00000000-0000-0000-0000-000000000002', 'executor@example.com')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO people (person_id, principal_id, full_name, linked_principal_id) \
+         VALUES ($1, '00000000-0000-0000-0000-000000000002', 'The deceased', \
+                 '00000000-0000-0000-0000-000000000001')",
+    )
+    .bind(deceased_person)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO deceased_estate_cases \
+         (case_id, deceased_person_id, executor_person_id, required_evidence_slots) \
+         VALUES ($1, $2, $3, ARRAY[]::text[])",
+    )
+    .bind(case_id)
+    .bind(deceased_person)
+    .bind(executor_person)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let app = vault_service::router();
+    let send = |method: &str, uri: String, token: String, body: Option<serde_json::Value>| {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+        axum::Router::into_service(app.clone()).oneshot(request)
+    };
+    let transfer = serde_json::json!({"case_id": case_id.to_string()});
+    let versions_uri = format!("/v1/documents/{document_id}/versions");
+
+    let response = send("GET", versions_uri.clone(), token_executor(), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = send(
+        "POST",
+        "/v1/documents/transfers".to_string(),
+        token_administrator(),
+        Some(transfer.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    sqlx::query("UPDATE cases SET status = 'exported' WHERE case_id = $1")
+        .bind(case_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let response = send(
+        "POST",
+        "/v1/documents/transfers".to_string(),
+        token_write(),
+        Some(transfer.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The executor has no people row yet, then one without an account.
+    let response = send(
+        "POST",
+        "/v1/documents/transfers".to_string(),
+        token_administrator(),
+        Some(transfer.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    sqlx::query(
+        "INSERT INTO people (person_id, principal_id, full_name) \
+         VALUES ($1, '00000000-0000-0000-0000-000000000002', 'The executor')",
+    )
+    .bind(executor_person)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let response = send(
+        "POST",
+        "/v1/documents/transfers".to_string(),
+        token_administrator(),
+        Some(transfer.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["code"], "person_not_linked");
+    sqlx::query(
+        "UPDATE people SET linked_principal_id = '00000000-0000-0000-0000-000000000002' \
+         WHERE person_id = $1",
+    )
+    .bind(executor_person)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let response = send(
+        "POST",
+        "/v1/documents/transfers".to_string(),
+        token_administrator(),
+        Some(transfer.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        value["grantee_principal_id"],
+        "00000000-0000-0000-0000-000000000002"
+    );
+    assert_eq!(
+        value["document_ids"],
+        serde_json::json!([document_id.to_string()])
+    );
+
+    let response = send("GET", versions_uri.clone(), token_executor(), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(
+        "POST",
+        format!("/v1/documents/{document_id}/verify"),
+        token_executor(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Re-running the handover is a no-op rather than a second set of audit events.
+    let response = send(
+        "POST",
+        "/v1/documents/transfers".to_string(),
+        token_administrator(),
+        Some(transfer),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["document_ids"], serde_json::json!([]));
+
+    let events: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM audit_events WHERE action = 'document.transferred' \
+         AND payload->>'document_id' = $1",
+    )
+    .bind(document_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+}
+
+#[tokio::test]
+async fn document_thumbnail_renders_caches_and_gates_by_tier() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-thumbnail");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+    let mut png = Vec::new();
+    image::RgbImage::from_pixel(600, 300, image::Rgb([10, 120, 200]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    let png_path = storage_dir.join("photo.png");
+    std::fs::write(&png_path, &png).unwrap();
+    let png_sha256 = hex::encode(sha2::Sha256::digest(&png));
+    let text_path = storage_dir.join("notes.txt");
+    std::fs::write(&text_path, b"hello").unwrap();
+
+    let mut documents = Vec::new();
+    for (sensitivity, path, sha256, mime_type) in [
+        ("amber", &png_path, png_sha256.as_str(), "image/png"),
+        ("red", &png_path, png_sha256.as_str(), "image/png"),
+        (
+            "amber",
+            &text_path,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            "text/plain",
+        ),
+    ] {
+        let document_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+             VALUES ('00000000-0000-0000-0000-000000000001', 'id', 'Scan', $1::sensitivity_tier) \
+             RETURNING document_id",
+        )
+        .bind(sensitivity)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let version_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO document_versions (document_id, blob_ref, sha256, byte_size, mime_type) \
+             VALUES ($1, $2, $3, 1, $4) RETURNING version_id",
+        )
+        .bind(document_id)
+        .bind(format!("file://{}", path.display()))
+        .bind(sha256)
+        .bind(mime_type)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        documents.push((document_id, version_id));
+    }
+    let (photo, photo_version) = documents[0];
+    let (red_photo, _) = documents[1];
+    let (notes, _) = documents[2];
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let thumbnail = |uri: String, etag: Option<String>| {
+            let mut request = Request::builder()
+                .method("GET")
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token_read()));
+            if let Some(etag) = etag {
+                request = request.header("if-none-match", etag);
+            }
+            axum::Router::into_service(app.clone()).oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = thumbnail(format!("/v1/documents/{photo}/thumbnail"), None)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "image/jpeg");
+        assert_eq!(response.headers()["cache-control"], "private, no-cache");
+        let etag = response.headers()["etag"].to_str().unwrap().to_string();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let rendered = image::load_from_memory(&body).unwrap();
+        assert_eq!((rendered.width(), rendered.height()), (256, 128));
+        let cached = storage_dir
+            .join("thumbnails")
+            .join(&png_sha256[..2])
+            .join(format!("{png_sha256}-256.jpg"));
+        assert_eq!(std::fs::read(&cached).unwrap(), body.to_vec());
+
+        let response = thumbnail(
+            format!("/v1/documents/{photo}/thumbnail?version_id={photo_version}"),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["cache-control"],
+            "private, max-age=31536000, immutable"
+        );
+
+        let response = thumbnail(format!("/v1/documents/{photo}/thumbnail"), Some(etag))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = thumbnail(format!("/v1/documents/{red_photo}/thumbnail"), None)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = thumbnail(format!("/v1/documents/{notes}/thumbnail"), None)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn bulk_init_and_commit_documents_are_all_or_nothing() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let storage_dir = unique_dir("vault-batch");
+    std::fs::create_dir_all(&storage_dir).unwrap();
+
+    with_env_async(&[("LOCAL_STORAGE_DIR", storage_dir.to_str())], || async {
+        let app = vault_service::router();
+        let post = |uri: &'static str, body: serde_json::Value| {
+            axum::Router::into_service(app.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {}", token_write()))
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+        };
+        let document_count = || async {
+            sqlx::query_scalar::<_, i64>("SELECT count(*) FROM documents")
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        };
+
+        let response = post(
+            "/v1/documents/batch",
+            serde_json::json!([
+                {"document_type": "will", "title": "Will", "sensitivity": "amber"},
+                {"document_type": "spaceship", "title": "Bad", "sensitivity": "amber"},
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(problem["detail"].as_str().unwrap().starts_with("entry 1:"));
+        assert_eq!(document_count().await, 0);
+
+        let response = post(
+            "/v1/documents/batch",
+            serde_json::json!([
+                {"document_type": "will", "title": "Will", "sensitivity": "amber"},
+                {"document_type": "policy", "title": "Policy", "sensitivity": "amber", "tags": ["insurance"]},
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let items = value["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(document_count().await, 2);
+        let document_ids: Vec<String> = items
+            .iter()
+            .map(|item| item["document_id"].as_str().unwrap().to_string())
+            .collect();
+        for (document_id, content) in document_ids.iter().zip(["will text", "policy text"]) {
+            std::fs::write(storage_dir.join(document_id), content).unwrap();
+        }
+        let will_sha = hex::encode(sha2::Sha256::digest("will text"));
+        let policy_sha = hex::encode(sha2::Sha256::digest("policy text"));
+
+        // The second entry names a document nobody owns, so neither version lands.
+        let response = post(
+            "/v1/documents/batch/commit",
+            serde_json::json!([
+                {"document_id": document_ids[0], "blob_ref": "auto", "sha256": will_sha, "byte_size": 9, "mime_type": "text/plain"},
+                {"document_id": Uuid::new_v4(), "blob_ref": "auto", "sha256": policy_sha, "byte_size": 11, "mime_type": "text/plain"},
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let versions: i64 = sqlx::query_scalar("SELECT count(*) FROM document_versions")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(versions, 0);
+
+        let response = post(
+            "/v1/documents/batch/commit",
+            serde_json::json!([
+                {"document_id": document_ids[0], "blob_ref": "auto", "sha256": will_sha, "byte_size": 9, "mime_type": "text/plain"},
+                {"document_id": document_ids[1], "blob_ref": "auto", "sha256": "not-a-sha", "byte_size": 11, "mime_type": "text/plain"},
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["detail"], "entry 1: invalid sha256");
+
+        // A well-formed digest that does not match the staged bytes is only caught once
+        // the blob is read, after the first entry has been written; it still rolls back.
+        let response = post(
+            "/v1/documents/batch/commit",
+            serde_json::json!([
+                {"document_id": document_ids[0], "blob_ref": "auto", "sha256": will_sha, "byte_size": 9, "mime_type": "text/plain"},
+                {"document_id": document_ids[1], "blob_ref": "auto", "sha256": will_sha, "byte_size": 11, "mime_type": "text/plain"},
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let versions: i64 = sqlx::query_scalar("SELECT count(*) FROM document_versions")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(versions, 0);
+        assert!(storage_dir.join(&document_ids[0]).exists());
+
+        let response = post(
+            "/v1/documents/batch/commit",
+            serde_json::json!([
+                {"document_id": document_ids[0], "blob_ref": "auto", "sha256": will_sha, "byte_size": 9, "mime_type": "text/plain"},
+                {"document_id": document_ids[1], "blob_ref": "auto", "sha256": policy_sha, "byte_size": 11, "mime_type": "text/plain"},
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let items = value["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["document_id"], document_ids[1].as_str());
+
+        let response = post("/v1/documents/batch", serde_json::json!([]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn list_documents_pages_with_cursor_and_total() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    // Two documents share a timestamp so the cursor has to break the tie on id; the red
+    // document is outside the reader's tiers and must not count towards the total.
+    for (created_at, sensitivity) in [
+        ("2025-01-01T00:00:00Z", "amber"),
+        ("2025-02-01T00:00:00Z", "amber"),
+        ("2025-02-01T00:00:00Z", "amber"),
+        ("2025-03-01T00:00:00Z", "amber"),
+        ("2025-04-01T00:00:00Z", "amber"),
+        ("2025-05-01T00:00:00Z", "red"),
+    ] {
+        sqlx::query(
+            "INSERT INTO documents (principal_id, document_type, title, sensitivity, created_at) \
+             VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'Will', $2::sensitivity_tier, $1::timestamptz)",
+        )
+        .bind(created_at)
+        .bind(sensitivity)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let app = vault_service::router();
+    let list = |uri: String| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let mut seen = Vec::new();
+    let mut uri = "/v1/documents?limit=2&with_total=true".to_string();
+    loop {
+        let response = list(uri).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["page"]["total"], 5);
+        assert_eq!(value["page"]["limit"], 2);
+        let items = value["items"].as_array().unwrap();
+        assert_eq!(value["page"]["returned"], items.len());
+        seen.extend(items.iter().map(|item| item["document_id"].clone()));
+        match value["page"]["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("/v1/documents?limit=2&with_total=true&cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(seen.len(), 5);
+    seen.sort_by_key(|id| id.to_string());
+    seen.dedup();
+    assert_eq!(seen.len(), 5);
+
+    let response = list("/v1/documents".to_string()).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(value["page"].get("total").is_none());
+    assert!(value["page"].get("next_cursor").is_none());
+    assert_eq!(value["page"]["returned"], 5);
+
+    let response = list("/v1/documents?cursor=garbage".to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn list_documents_filters_by_created_window() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let mut document_ids = Vec::new();
+    for created_at in [
+        "2025-01-01T00:00:00Z",
+        "2025-02-01T00:00:00Z",
+        "2025-03-01T00:00:00Z",
+    ] {
+        let document_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO documents (principal_id, document_type, title, sensitivity, created_at) \
+             VALUES ('00000000-0000-0000-0000-000000000001', 'will', 'Will', 'amber', $1::timestamptz) \
+             RETURNING document_id",
+        )
+        .bind(created_at)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        document_ids.push(document_id.to_string());
+    }
+
+    let app = vault_service::router();
+    let list = |uri: &'static str| {
+        axum::Router::into_service(app.clone()).oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token_read()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = list(
+        "/v1/documents?created_after=2025-01-15T00:00:00Z&created_before=2025-03-01T00:00:00Z",
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let items = value["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["document_id"], document_ids[1].as_str());
+
+    let response = list(
+        "/v1/documents?created_after=2025-03-01T00:00:00Z&created_before=2025-01-01T00:00:00Z",
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = list("/v1/documents?created_before=last-week")
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn document_usages_lists_referencing_case_slots() {
+    init_env();
+    let pool = match setup_db().await {
+        Some(pool) => pool,
+        None => return,
+    };
+    reset_db(&pool).await.unwrap();
+
+    let mut document_ids = Vec::new();
+    for title in ["Living will", "Orphan"] {
+        let document_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO documents (principal_id, document_type, title, sensitivity) \
+             VALUES ('00000000-0000-0000-0000-000000000001', 'advance_directive', $1, 'amber') \
+             RETURNING document_id",
+        )
+        .bind(title)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        document_ids.push(document_id);
+    }
+    let (document_id, orphan_id) = (document_ids[0], document_ids[1]);
+
+    let mut case_ids = Vec::new();
+    for case_type in ["emergency_pack", "death_readiness", "mhca39"] {
+        let case_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO cases (principal_id, case_type) \
+             VALUES ('00000000-0000-0000-0000-000000000001', $1::case_type) RETURNING case_id",
+        )
+        .bind(case_type)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        case_ids.push(case_id);
+    }
+    sqlx::query(
+        "INSERT INTO emergency_pack_cases (case_id, directive_document_ids) VALUES ($1, $2)",
+    )
+    .bind(case_ids[0])
+    .bind(vec![document_id])
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO death_readiness_cases (case_id, executor_nominee_person_id, asset_document_ids) \
+         VALUES ($1, uuid_generate_v4(), $2)",
+    )
+    .bind(case_ids[1])
+    .bind(vec![document_id])
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO mhca39_evidence (case_id, slot_name, document_id) VALUES ($1, 'medical_report', $2)",
+    )
+    .bind(case_ids[2])
+    .bind(document_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE cases SET archived_at = now() WHERE case_id = $1")
+        .bind(case_ids[2])
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let app = vault_service::router();
+    let usages = |document_id: Uuid| {
+        Request::builder()
+            .uri(format!("/v1/documents/{document_id}/usages"))
+            .header("authorization", format!("Bearer {}", token_read()))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = axum::Router::into_service(app.clone())
+        .oneshot(usages(document_id))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let mut found: Vec<(String, String, bool)> = value["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            (
+                item["case_id"].as_str().unwrap().to_string(),
+                item["slot"].as_str().unwrap().to_string(),
+                item["archived"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    found.sort();
+    let mut expected = vec![
+        (
+            case_ids[0].to_string(),
+            "directive_documents".to_string(),
+            false,
+        ),
+        (
+            case_ids[1].to_string(),
+            "asset_documents".to_string(),
+            false,
+        ),
+        (case_ids[2].to_string(), "medical_report".to_string(), true),
+    ];
+    expected.sort();
+    assert_eq!(found, expected);
+
+    let response = axum::Router::into_service(app)
+        .oneshot(usages(orphan_id))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["items"], serde_json::json!([]));
+}
@@ -13,6 +13,8 @@ fn proxy_denied_red_document() {
         scopes: vec!["read:all".into()],
         expires_at: chrono::Utc::now(),
         email: None,
+        acting_for: None,
+        org_id: None,
     };
     let request_id = RequestId(Uuid::new_v4());
 